@@ -0,0 +1,79 @@
+use std::{env, fs, path::Path};
+
+// Every `src/days/yYYYY/dayN.rs` is declared as a module and joins the registry in
+// `days::registry`, so adding a day is adding its file. A year's `mod.rs`, if it has
+// one, includes the generated `yYYYY.rs` and declares the modules its days share.
+fn main() {
+    println!("cargo:rerun-if-changed=src/days");
+    let out_dir = env::var("OUT_DIR").expect("set by cargo");
+    let out = Path::new(&out_dir);
+    let root = env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    let days_dir = Path::new(&root).join("src/days");
+
+    let mut days: Vec<(u32, u32)> = numbered("src/days", "y", "")
+        .into_iter()
+        .flat_map(|year| {
+            numbered(&format!("src/days/y{year}"), "day", ".rs")
+                .into_iter()
+                .map(move |day| (year, day))
+        })
+        .collect();
+    days.sort();
+
+    let entries: String = days
+        .iter()
+        .map(|(year, day)| {
+            format!(
+                "    Entry {{ year: {year}, day: {day}, program: &y{year}::day{day}::Instance }},\n"
+            )
+        })
+        .collect();
+
+    fs::write(out.join("registry.rs"), format!("&[\n{entries}]\n")).expect("writable OUT_DIR");
+
+    let mut years: Vec<u32> = days.iter().map(|&(year, _)| year).collect();
+    years.dedup();
+    let mut year_modules = String::new();
+    for year in years {
+        let year_dir = days_dir.join(format!("y{year}"));
+        let modules: String = days
+            .iter()
+            .filter(|&&(y, _)| y == year)
+            .map(|(_, day)| {
+                let path = year_dir.join(format!("day{day}.rs"));
+                format!(
+                    "#[path = {:?}]\npub mod day{day};\n",
+                    path.display().to_string()
+                )
+            })
+            .collect();
+        fs::write(out.join(format!("y{year}.rs")), modules).expect("writable OUT_DIR");
+
+        let mod_rs = year_dir.join("mod.rs");
+        if mod_rs.exists() {
+            year_modules += &format!(
+                "#[path = {:?}]\npub mod y{year};\n",
+                mod_rs.display().to_string()
+            );
+        } else {
+            year_modules += &format!(
+                "pub mod y{year} {{\n    include!(concat!(env!(\"OUT_DIR\"), \"/y{year}.rs\"));\n}}\n"
+            );
+        }
+    }
+    fs::write(out.join("years.rs"), year_modules).expect("writable OUT_DIR");
+}
+
+/// Numbers `N` of the entries in `dir` named `<prefix>N<suffix>`.
+fn numbered(dir: &str, prefix: &str, suffix: &str) -> Vec<u32> {
+    fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("can't read {dir}: {e}"))
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use crate::{
+    days::{
+        day::{Context, Part, Program, Timings},
+        Entry,
+    },
+    error::{Error, ParseError},
+    panics,
+};
+
+/// Summary of repeated measurements of one stage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn of(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Runs `program` `warmup` times without measuring, then `runs` times keeping the timings.
+pub fn bench(
+    program: &dyn Program,
+    lines: &[String],
+    part: Option<Part>,
+    context: &Context,
+    warmup: usize,
+    runs: usize,
+) -> Result<Vec<Timings>, Error> {
+    // A part 2 failure has no timing to measure, so it fails the benchmark as a whole.
+    let run = || {
+        let result = panics::catch(|| program.run(lines, part, context))?;
+        match result.error {
+            Some(error) => Err(error),
+            None => Ok(result),
+        }
+    };
+
+    for _ in 0..warmup {
+        run()?;
+    }
+    (0..runs)
+        .map(|_| run().map(|result| result.timings))
+        .collect()
+}
+
+type Stage = fn(&Timings) -> Option<Duration>;
+
+/// Statistics for each stage that ran, in the order parse, part 1, part 2, total.
+pub fn stages(timings: &[Timings]) -> Vec<(&'static str, Stats)> {
+    let stages: [(&str, Stage); 4] = [
+        ("parse", |t| Some(t.parse)),
+        ("part1", |t| t.part1),
+        ("part2", |t| t.part2),
+        ("total", |t| Some(t.total())),
+    ];
+    stages
+        .into_iter()
+        .filter_map(|(name, stage)| {
+            let samples: Option<Vec<_>> = timings.iter().map(stage).collect();
+            samples
+                .filter(|samples| !samples.is_empty())
+                .map(|samples| (name, Stats::of(&samples)))
+        })
+        .collect()
+}
+
+/// Median time of each day's stages from an earlier run, stored one
+/// `<year>/<day> <stage> <nanoseconds>` entry per line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline(BTreeMap<(u32, u32, String), Duration>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, Error> {
+        let text = fs::read_to_string(path)?;
+        Baseline::parse(&text)
+            .map_err(|e| Error::from(e).with_source(&path.display().to_string(), &text))
+    }
+
+    /// Loads `path` if it exists, so new results can be merged into it.
+    pub fn load_or_default(path: &Path) -> Result<Baseline, Error> {
+        match Baseline::load(path) {
+            Err(Error::Io(e)) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            result => result,
+        }
+    }
+
+    fn parse(text: &str) -> Result<Baseline, ParseError> {
+        let mut baseline = Baseline::default();
+        for (i, line) in text.lines().enumerate() {
+            let error = |message: &str, token| {
+                ParseError::new(message)
+                    .on_line(i + 1)
+                    .spanning(line, token)
+            };
+            let words: Vec<_> = line.split_whitespace().collect();
+            let [id, stage, nanos] = words[..] else {
+                return Err(error("expected `<year>/<day> <stage> <nanoseconds>`", line));
+            };
+            let (year, day) = id
+                .split_once('/')
+                .and_then(|(year, day)| Some((year.parse().ok()?, day.parse().ok()?)))
+                .ok_or_else(|| error("expected `<year>/<day>`", id))?;
+            let nanos = nanos
+                .parse()
+                .map_err(|_| error("invalid nanoseconds", nanos))?;
+            baseline
+                .0
+                .insert((year, day, stage.to_owned()), Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let text: String = self
+            .0
+            .iter()
+            .map(|((year, day, stage), median)| {
+                format!("{}/{} {} {}\n", year, day, stage, median.as_nanos())
+            })
+            .collect();
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    pub fn get(&self, entry: &Entry, stage: &str) -> Option<Duration> {
+        self.0
+            .get(&(entry.year, entry.day, stage.to_owned()))
+            .copied()
+    }
+
+    pub fn insert(&mut self, entry: &Entry, stage: &str, median: Duration) {
+        self.0
+            .insert((entry.year, entry.day, stage.to_owned()), median);
+    }
+}
+
+/// The median time of `stage` among `stages`, if it ran.
+pub fn median(stages: &[(&str, Stats)], stage: &str) -> Option<Duration> {
+    stages
+        .iter()
+        .find(|(name, _)| *name == stage)
+        .map(|(_, stats)| stats.median)
+}
+
+/// Prints `stages` under `heading`, with the change in median time against the time
+/// `reference` gives for each stage.
+pub fn print(
+    heading: &str,
+    stages: &[(&str, Stats)],
+    reference: impl Fn(&str) -> Option<Duration>,
+) {
+    println!("{}", heading);
+    println!(
+        "{:<6} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "Stage", "Min", "Median", "Mean", "Stddev", "Change"
+    );
+    for (stage, stats) in stages {
+        let change = reference(stage).map_or("-".to_owned(), |base| change(base, stats.median));
+        println!(
+            "{:<6} {:>10} {:>10} {:>10} {:>10} {:>8}",
+            stage,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev),
+            change
+        );
+    }
+    println!();
+}
+
+fn change(base: Duration, now: Duration) -> String {
+    if base.is_zero() {
+        return "-".to_owned();
+    }
+    let percent = (now.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0;
+    format!("{:+.1}%", percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::of(&samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn baseline() {
+        let baseline = Baseline::parse("2017/15 part1 2000\n2017/15 total 2500\n").unwrap();
+        let entry = days::get(2017, 15).unwrap();
+        assert_eq!(
+            baseline.get(entry, "part1"),
+            Some(Duration::from_nanos(2000))
+        );
+        assert_eq!(baseline.get(entry, "part2"), None);
+        assert_eq!(
+            change(Duration::from_nanos(2000), Duration::from_nanos(1500)),
+            "-25.0%"
+        );
+
+        let error = Baseline::parse("2017/1 parse 10\n1 parse 10").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected `<year>/<day>`"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fmt, fs, io, path::Path};
+
+use crate::{
+    days::day::{DayResult, Example, Program},
+    error::Error,
+    panics,
+};
+
+/// How an answer compares with the one stored for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+/// Known answers for a day, read from a `dayN.answer` file with part 1 on the
+/// first line and part 2 on the second. Blank lines leave that part unknown.
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn read(path: &Path) -> Result<Answers, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Answers::parse(&text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    fn parse(text: &str) -> Answers {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .map(|line| (!line.is_empty()).then(|| line.to_owned()));
+        Answers {
+            part1: lines.next().flatten(),
+            part2: lines.next().flatten(),
+        }
+    }
+
+    /// Compares each part that ran with its stored answer.
+    pub fn check(&self, result: &DayResult) -> [Option<Check>; 2] {
+        [(&result.part1, &self.part1), (&result.part2, &self.part2)].map(|(actual, expected)| {
+            actual.as_ref().map(|actual| match expected {
+                Some(expected) if expected == actual => Check::Pass,
+                Some(expected) => Check::Fail {
+                    expected: expected.clone(),
+                },
+                None => Check::Missing,
+            })
+        })
+    }
+}
+
+/// One part's answer to an example, and how it compares with the puzzle text's.
+#[derive(Debug)]
+pub struct ExampleAnswer {
+    pub answer: Option<String>,
+    pub check: Check,
+}
+
+/// Runs `example` with `program`, checking the parts the example gives answers for; a
+/// part that gives no answer fails. A failing or panicking part is an error.
+pub fn check_example(
+    program: &dyn Program,
+    example: &Example,
+) -> Result<[Option<ExampleAnswer>; 2], Error> {
+    let result =
+        panics::catch(|| program.run(&example.lines(), example.part(), &example.context()))?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+
+    let parts = [(result.part1, example.part1), (result.part2, example.part2)];
+    Ok(parts.map(|(answer, expected)| {
+        let expected = expected?;
+        let check = if answer.as_deref() == Some(expected) {
+            Check::Pass
+        } else {
+            Check::Fail {
+                expected: expected.to_owned(),
+            }
+        };
+        Some(ExampleAnswer { answer, check })
+    }))
+}
+
+impl Check {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Check::Fail { .. })
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail { expected } => write!(f, "fail, expected {}", expected),
+            Check::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day::Timings;
+
+    #[test]
+    fn check() {
+        let answers = Answers::parse("42\n\n");
+        let result = DayResult {
+            part1: Some("41".to_owned()),
+            part2: Some("7".to_owned()),
+            timings: Timings::default(),
+            error: None,
+        };
+
+        assert_eq!(
+            answers.check(&result),
+            [
+                Some(Check::Fail {
+                    expected: "42".to_owned()
+                }),
+                Some(Check::Missing)
+            ]
+        );
+
+        let answers = Answers::parse("41\n7\n");
+        assert_eq!(
+            answers.check(&result),
+            [Some(Check::Pass), Some(Check::Pass)]
+        );
+    }
+}
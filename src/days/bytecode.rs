@@ -0,0 +1,393 @@
+use std::fmt;
+
+use super::{
+    day::{Context, Error, Param},
+    vm::{Factors, InstructionSet, Operand, Register, Registers, Value},
+};
+
+pub use super::vm::SLICE;
+
+/// Declared by the days that can run either way, so `bench --compare bytecode=0`
+/// can compare the two.
+pub const PARAM: Param = Param::new(
+    "bytecode",
+    1,
+    "Run compiled bytecode (1) or the interpreter (0)",
+);
+
+/// Whether a day declaring [`PARAM`] runs compiled. Traces come from the
+/// interpreter, so tracing always interprets.
+pub fn enabled(context: &Context) -> Result<bool, Error> {
+    Ok(context.param(PARAM.name)? != 0 && context.trace.is_none())
+}
+
+/// One instruction of the compiled form of an assembly dialect. Operands are split
+/// into register and value variants and jump targets are absolute, so running an
+/// operation never has to look at how it was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Set(Register, Register),
+    SetValue(Register, Value),
+    Add(Register, Register),
+    AddValue(Register, Value),
+    Sub(Register, Register),
+    Mul(Register, Register),
+    MulValue(Register, Value),
+    Mod(Register, Register),
+    ModValue(Register, Value),
+    Jump(usize),
+    JumpNonZero(Register, usize),
+    JumpPositive(Register, usize),
+    /// Jumps whose offset is only known when they run, relative to themselves.
+    JumpNonZeroBy(Operand, Register),
+    JumpPositiveBy(Operand, Register),
+    Send(Register),
+    SendValue(Value),
+    Receive(Register),
+    /// Runs a trial multiplication loop at once and continues at the target, or sets
+    /// `scratch` to `factor` like the instruction it replaced if the loop never ends.
+    Divides(Factors, usize),
+    /// Runs the loop around [`Op::Divides`] at once and continues at the target, or
+    /// sets `counter` to the operand like the instruction it replaced.
+    IsComposite(Factors, Operand, usize),
+    /// Ends every program; jumps leaving the program go here.
+    Halt,
+}
+
+/// When a conditional jump is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Test {
+    NonZero,
+    Positive,
+}
+
+impl Test {
+    fn holds(self, value: Value) -> bool {
+        match self {
+            Test::NonZero => value != 0,
+            Test::Positive => value > 0,
+        }
+    }
+}
+
+/// An instruction set [`compile`] can lower to [`Op`]s.
+pub trait Lower: InstructionSet {
+    /// The operation for this instruction, which sits at `at` of the program.
+    fn lower(&self, at: &Lowering) -> Op;
+}
+
+/// Where an instruction being lowered sits, with the operations its parts become.
+pub struct Lowering {
+    pc: usize,
+    len: usize,
+}
+
+impl Lowering {
+    pub fn set(&self, register: Register, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Set(register, source),
+            Operand::Value(value) => Op::SetValue(register, value),
+        }
+    }
+
+    pub fn add(&self, register: Register, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Add(register, source),
+            Operand::Value(value) => Op::AddValue(register, value),
+        }
+    }
+
+    pub fn sub(&self, register: Register, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Sub(register, source),
+            Operand::Value(value) => Op::AddValue(register, -value),
+        }
+    }
+
+    pub fn mul(&self, register: Register, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Mul(register, source),
+            Operand::Value(value) => Op::MulValue(register, value),
+        }
+    }
+
+    pub fn modulo(&self, register: Register, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Mod(register, source),
+            Operand::Value(value) => Op::ModValue(register, value),
+        }
+    }
+
+    pub fn send(&self, value: Operand) -> Op {
+        match value {
+            Operand::Register(source) => Op::Send(source),
+            Operand::Value(value) => Op::SendValue(value),
+        }
+    }
+
+    pub fn receive(&self, register: Register) -> Op {
+        Op::Receive(register)
+    }
+
+    /// Jumps by `offset` if `value` passes `test`; constant tests become plain jumps.
+    pub fn jump(&self, test: Test, value: Operand, offset: Operand) -> Op {
+        if let Operand::Value(value) = value {
+            if !test.holds(value) {
+                return Op::Jump(self.pc + 1);
+            }
+        }
+        let offset = match offset {
+            Operand::Value(offset) => offset,
+            Operand::Register(offset) => {
+                return match test {
+                    Test::NonZero => Op::JumpNonZeroBy(value, offset),
+                    Test::Positive => Op::JumpPositiveBy(value, offset),
+                }
+            }
+        };
+        let target = self.target(offset);
+        match (test, value) {
+            (_, Operand::Value(_)) => Op::Jump(target),
+            (Test::NonZero, Operand::Register(register)) => Op::JumpNonZero(register, target),
+            (Test::Positive, Operand::Register(register)) => Op::JumpPositive(register, target),
+        }
+    }
+
+    /// The target of a jump by `offset` from this instruction.
+    pub fn target(&self, offset: Value) -> usize {
+        target(self.pc, offset, self.len)
+    }
+}
+
+/// The instruction `offset` away from `pc`, or the final [`Op::Halt`] at `len` if
+/// that is outside the program.
+fn target(pc: usize, offset: Value, len: usize) -> usize {
+    pc.checked_add_signed(offset as isize)
+        .filter(|&target| target < len)
+        .unwrap_or(len)
+}
+
+/// A compiled program: one operation per instruction, followed by [`Op::Halt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bytecode(Vec<Op>);
+
+impl Bytecode {
+    pub fn ops(&self) -> &[Op] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Bytecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (pc, op) in self.0.iter().enumerate() {
+            writeln!(f, "{:>4}  {:?}", pc, op)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn compile<I: Lower>(program: &[I]) -> Bytecode {
+    let len = program.len();
+    let ops = program
+        .iter()
+        .enumerate()
+        .map(|(pc, instruction)| instruction.lower(&Lowering { pc, len }))
+        .chain([Op::Halt])
+        .collect();
+    Bytecode(ops)
+}
+
+/// Why [`Processor::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A value was sent; running again continues after the send.
+    Send(Value),
+    /// The program waits on a receive into the register, until [`Processor::receive`]
+    /// or [`Processor::skip`] lets it continue.
+    Receive(Register),
+    /// The step budget ran out; running again continues where it stopped.
+    Paused,
+    Halted,
+}
+
+/// Runs [`Bytecode`], handing sends and receives to the caller so each dialect can
+/// give them its own meaning.
+pub struct Processor<'a> {
+    pub registers: Registers,
+    /// Multiplications executed so far.
+    pub muls: u64,
+    code: &'a [Op],
+    pc: usize,
+}
+
+impl<'a> Processor<'a> {
+    pub fn new(code: &'a Bytecode) -> Processor<'a> {
+        Processor {
+            registers: Registers::default(),
+            muls: 0,
+            code: &code.0,
+            pc: 0,
+        }
+    }
+
+    /// Runs until the program sends, receives or halts, or has executed `budget`
+    /// operations so the caller can check for cancellation.
+    pub fn run(&mut self, budget: u64) -> Event {
+        let registers = &mut self.registers;
+        let halt = self.code.len() - 1;
+        let mut pc = self.pc;
+        let mut left = budget;
+        let event = loop {
+            if left == 0 {
+                break Event::Paused;
+            }
+            left -= 1;
+            match self.code[pc] {
+                Op::Set(register, source) => registers[register] = registers[source],
+                Op::SetValue(register, value) => registers[register] = value,
+                Op::Add(register, source) => registers[register] += registers[source],
+                Op::AddValue(register, value) => registers[register] += value,
+                Op::Sub(register, source) => registers[register] -= registers[source],
+                Op::Mul(register, source) => {
+                    registers[register] *= registers[source];
+                    self.muls += 1;
+                }
+                Op::MulValue(register, value) => {
+                    registers[register] *= value;
+                    self.muls += 1;
+                }
+                Op::Mod(register, source) => registers[register] %= registers[source],
+                Op::ModValue(register, value) => registers[register] %= value,
+                Op::Jump(target) => {
+                    pc = target;
+                    continue;
+                }
+                Op::JumpNonZero(register, target) => {
+                    if registers[register] != 0 {
+                        pc = target;
+                        continue;
+                    }
+                }
+                Op::JumpPositive(register, target) => {
+                    if registers[register] > 0 {
+                        pc = target;
+                        continue;
+                    }
+                }
+                Op::JumpNonZeroBy(value, offset) => {
+                    if registers.resolve(value) != 0 {
+                        pc = target(pc, registers[offset], halt);
+                        continue;
+                    }
+                }
+                Op::JumpPositiveBy(value, offset) => {
+                    if registers.resolve(value) > 0 {
+                        pc = target(pc, registers[offset], halt);
+                        continue;
+                    }
+                }
+                Op::Send(source) => {
+                    pc += 1;
+                    break Event::Send(registers[source]);
+                }
+                Op::SendValue(value) => {
+                    pc += 1;
+                    break Event::Send(value);
+                }
+                Op::Divides(factors, exit) => match factors.divides(registers) {
+                    Some(muls) => {
+                        self.muls += muls;
+                        pc = exit;
+                        continue;
+                    }
+                    None => registers[factors.scratch] = registers[factors.factor],
+                },
+                Op::IsComposite(factors, start, exit) => {
+                    match factors.is_composite(registers, start) {
+                        Some(muls) => {
+                            self.muls += muls;
+                            pc = exit;
+                            continue;
+                        }
+                        None => registers[factors.counter] = registers.resolve(start),
+                    }
+                }
+                Op::Receive(register) => break Event::Receive(register),
+                Op::Halt => break Event::Halted,
+            }
+            pc += 1;
+        };
+        self.pc = pc;
+        event
+    }
+
+    /// Completes the pending receive with `value`.
+    pub fn receive(&mut self, value: Value) {
+        if let Op::Receive(register) = self.code[self.pc] {
+            self.registers[register] = value;
+            self.pc += 1;
+        }
+    }
+
+    /// Continues past the pending receive without a value.
+    pub fn skip(&mut self) {
+        if let Op::Receive(_) = self.code[self.pc] {
+            self.pc += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{
+        day::parse_lines,
+        y2017::{day18, day23},
+    };
+
+    fn register(name: char) -> Register {
+        Register::named(name).unwrap()
+    }
+
+    #[test]
+    fn lowering() {
+        let lines = [
+            "set a 3", "sub a b", "sub a 2", "jnz a -2", "jnz 1 3", "jnz 0 a", "jnz b c",
+        ]
+        .map(str::to_owned);
+        let program: Vec<day23::Instruction> = parse_lines(&lines).unwrap();
+        let (a, b, c) = (register('a'), register('b'), register('c'));
+        assert_eq!(
+            compile(&program).ops(),
+            [
+                Op::SetValue(a, 3),
+                Op::Sub(a, b),
+                Op::AddValue(a, -2),
+                Op::JumpNonZero(a, 1),
+                Op::Jump(7),
+                Op::Jump(6),
+                Op::JumpNonZeroBy(Operand::Register(b), c),
+                Op::Halt,
+            ]
+        );
+    }
+
+    #[test]
+    fn send_and_receive() {
+        let lines =
+            ["snd 7", "rcv a", "mul a 2", "snd a", "set b -10", "jgz 1 b"].map(str::to_owned);
+        let program: Vec<day18::Instruction> = parse_lines(&lines).unwrap();
+        let code = compile(&program);
+        let mut processor = Processor::new(&code);
+
+        assert_eq!(processor.run(SLICE), Event::Send(7));
+        assert_eq!(processor.run(SLICE), Event::Receive(register('a')));
+        // Still waiting until the caller answers.
+        assert_eq!(processor.run(SLICE), Event::Receive(register('a')));
+        processor.receive(5);
+        assert_eq!(processor.run(1), Event::Paused);
+        assert_eq!(processor.run(SLICE), Event::Send(10));
+        assert_eq!(processor.run(SLICE), Event::Halted);
+        assert_eq!(processor.muls, 1);
+    }
+}
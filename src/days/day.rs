@@ -1,8 +1,372 @@
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use super::vm::{Debuggee, Trace};
+pub use crate::error::{Error, ParseError};
+use crate::panics;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
 pub struct DayResult {
-    pub part1: String,
+    pub part1: Option<String>,
     pub part2: Option<String>,
+    pub timings: Timings,
+    /// Why part 2 failed after part 1 answered; part 1's answer is kept.
+    pub error: Option<Error>,
+}
+
+/// Wall-clock time spent in each stage; parts that didn't run have no timing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+/// `Send + Sync` so `--jobs` can run days on several threads.
+pub trait Day: Send + Sync {
+    type Input;
+
+    /// Days that take a noticeable time on a real input, left out by the `fast` selector.
+    const SLOW: bool = false;
+
+    /// Worked examples from the puzzle text, checked by the tests and `examples`.
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Sizes the puzzle fixes but examples and experiments want to change, read with
+    /// [`Context::param`].
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError>;
+
+    fn part1(&self, input: &Self::Input, context: &Context) -> Result<String, Error>;
+
+    /// Days without a second puzzle keep the default.
+    fn part2(&self, _input: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    /// Days that run an assembly program hand it to the `debug` subcommand, ready to
+    /// run the given part.
+    fn debug(&self, _input: &Self::Input, _part: Part) -> Option<Box<dyn Debuggee>> {
+        None
+    }
+}
+
+/// A number a day reads instead of hard-coding it, set with `--param name=value`.
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    pub description: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: usize, description: &'static str) -> Param {
+        Param {
+            name,
+            default,
+            description,
+        }
+    }
+}
+
+/// A puzzle example with the answers it should give; parts left as `None` aren't run.
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// Parameters the puzzle text uses for this example instead of the defaults.
+    pub params: &'static [(&'static str, usize)],
+}
+
+impl Example {
+    pub const fn new(input: &'static str, part1: &'static str, part2: &'static str) -> Example {
+        Example {
+            input,
+            part1: Some(part1),
+            part2: Some(part2),
+            params: &[],
+        }
+    }
+
+    pub const fn part_one(input: &'static str, answer: &'static str) -> Example {
+        Example {
+            input,
+            part1: Some(answer),
+            part2: None,
+            params: &[],
+        }
+    }
+
+    pub const fn part_two(input: &'static str, answer: &'static str) -> Example {
+        Example {
+            input,
+            part1: None,
+            part2: Some(answer),
+            params: &[],
+        }
+    }
+
+    pub const fn with_params(self, params: &'static [(&'static str, usize)]) -> Example {
+        Example { params, ..self }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.input.lines().map(str::to_owned).collect()
+    }
+
+    /// The part to run, or `None` for both.
+    pub fn part(&self) -> Option<Part> {
+        match (self.part1, self.part2) {
+            (Some(_), None) => Some(Part::One),
+            (None, Some(_)) => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// A context carrying the example's parameters.
+    pub fn context(&self) -> Context {
+        let mut context = Context::default();
+        for (name, value) in self.params {
+            context.params.set(name, *value);
+        }
+        context
+    }
+}
+
+/// What the runner hands to each part besides its input.
+#[derive(Clone, Default)]
+pub struct Context {
+    pub cancel: Cancel,
+    pub params: Params,
+    /// Where interpreters record the instructions they execute, if anywhere.
+    pub trace: Option<Trace>,
 }
 
-pub trait Day {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String>;
+impl Context {
+    /// The value of one of the day's declared parameters.
+    pub fn param(&self, name: &str) -> Result<usize, Error> {
+        self.params
+            .get(name)
+            .ok_or_else(|| Error::solve(format!("parameter `{}` is not declared", name)))
+    }
+}
+
+/// Parameter values by name. The runner passes the ones given on the command line,
+/// [`Program::run`] fills in the defaults of the ones a day declares.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(BTreeMap<String, usize>);
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.0.get(name).copied()
+    }
+
+    pub fn set(&mut self, name: &str, value: usize) {
+        self.0.insert(name.to_owned(), value);
+    }
+
+    /// The declared parameters, taking values from `self` where given and defaults otherwise.
+    fn resolve(&self, declared: &[Param]) -> Params {
+        Params(
+            declared
+                .iter()
+                .map(|param| {
+                    let value = self.get(param.name).unwrap_or(param.default);
+                    (param.name.to_owned(), value)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Loop iterations between two [`Cancel::check`]s, rare enough to cost nothing and
+/// frequent enough that a timeout stops a day within milliseconds.
+pub const CHECK_EVERY: usize = 1 << 20;
+
+/// Cooperative cancellation: the runner cancels it, long-running loops call
+/// [`Cancel::check`] and bail out with [`Error::Cancelled`].
+#[derive(Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            Err(Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Object-safe view of a [`Day`] so days with different inputs can share a registry.
+pub trait Program: Send + Sync {
+    /// Parses the input once, then runs both parts, or only the given one. If part 2
+    /// fails, panics included, after part 1 answered, the result keeps part 1's answer
+    /// and holds the failure in [`DayResult::error`].
+    fn run(
+        &self,
+        lines: &[String],
+        part: Option<Part>,
+        context: &Context,
+    ) -> Result<DayResult, Error> {
+        self.run_partial(lines, part, context, &mut |_| {})
+    }
+
+    /// [`Program::run`], handing part 1's answer to `partial` before part 2 starts so
+    /// a caller that stops waiting for part 2 still has it.
+    fn run_partial(
+        &self,
+        lines: &[String],
+        part: Option<Part>,
+        context: &Context,
+        partial: &mut dyn FnMut(&DayResult),
+    ) -> Result<DayResult, Error>;
+
+    fn slow(&self) -> bool;
+
+    fn examples(&self) -> &'static [Example];
+
+    fn params(&self) -> &'static [Param];
+
+    /// Parses the input and sets up the day's program for the debugger, if it has one.
+    fn debug(&self, lines: &[String], part: Part) -> Result<Option<Box<dyn Debuggee>>, Error>;
+}
+
+impl<D: Day> Program for D {
+    fn run_partial(
+        &self,
+        lines: &[String],
+        part: Option<Part>,
+        context: &Context,
+        partial: &mut dyn FnMut(&DayResult),
+    ) -> Result<DayResult, Error> {
+        let mut timings = Timings::default();
+        let context = &Context {
+            params: context.params.resolve(D::PARAMS),
+            ..context.clone()
+        };
+
+        let start = Instant::now();
+        let input = self.parse(lines)?;
+        timings.parse = start.elapsed();
+
+        let part1 = match part {
+            None | Some(Part::One) => {
+                let start = Instant::now();
+                let answer = self.part1(&input, context)?;
+                timings.part1 = Some(start.elapsed());
+                Some(answer)
+            }
+            Some(Part::Two) => None,
+        };
+        let mut error = None;
+        let part2 = match part {
+            None | Some(Part::Two) => {
+                if part1.is_some() {
+                    partial(&DayResult {
+                        part1: part1.clone(),
+                        part2: None,
+                        timings,
+                        error: None,
+                    });
+                }
+                let start = Instant::now();
+                match panics::catch(|| self.part2(&input, context)) {
+                    Ok(answer) => {
+                        timings.part2 = answer.as_ref().map(|_| start.elapsed());
+                        answer
+                    }
+                    Err(e) if part1.is_some() => {
+                        error = Some(e);
+                        None
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Some(Part::One) => None,
+        };
+
+        Ok(DayResult {
+            part1,
+            part2,
+            timings,
+            error,
+        })
+    }
+
+    fn slow(&self) -> bool {
+        D::SLOW
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        D::EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        D::PARAMS
+    }
+
+    fn debug(&self, lines: &[String], part: Part) -> Result<Option<Box<dyn Debuggee>>, Error> {
+        let input = self.parse(lines)?;
+        Ok(Day::debug(self, &input, part))
+    }
+}
+
+/// Parses every line on its own, tagging failures with their 1-based line number.
+pub fn parse_lines<T: FromStr<Err = ParseError>>(lines: &[String]) -> Result<Vec<T>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Runs every example of `program` and checks the answers it gives.
+#[cfg(test)]
+pub fn assert_examples(program: &dyn Program) {
+    use crate::check::{check_example, ExampleAnswer};
+
+    for (i, example) in program.examples().iter().enumerate() {
+        let answers = check_example(program, example)
+            .unwrap_or_else(|e| panic!("example {} failed: {}", i + 1, e));
+        for (n, answer) in (1..).zip(answers) {
+            let Some(ExampleAnswer { answer, check }) = answer else {
+                continue;
+            };
+            assert!(
+                !check.is_fail(),
+                "example {} part {}: {} ({})",
+                i + 1,
+                n,
+                answer.as_deref().unwrap_or("no answer"),
+                check
+            );
+        }
+    }
 }
@@ -1,31 +1,77 @@
+pub mod bytecode;
 pub mod day;
+pub mod vm;
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-
-mod knot;
+// The `yYYYY` modules are declared by the build script.
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
+
+use std::{fmt, ops::RangeInclusive};
+
+use day::Program;
+
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub program: &'static dyn Program,
+}
+
+const REGISTRY: &[Entry] = include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Shown as `year/day`, the way selectors name a single day.
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// All registered days, ordered by year and day.
+pub fn registry() -> &'static [Entry] {
+    REGISTRY
+}
+
+pub fn get(year: u32, day: u32) -> Option<&'static Entry> {
+    REGISTRY
+        .iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+/// The year bare day numbers refer to: the latest one that registers any of `days`, or
+/// the [latest year](latest_year) if none does.
+pub fn default_year(days: RangeInclusive<u32>) -> u32 {
+    year_registering(REGISTRY, days)
+}
+
+/// The latest year with a registered day.
+pub fn latest_year() -> u32 {
+    REGISTRY.last().map_or(0, |entry| entry.year)
+}
+
+fn year_registering(registry: &[Entry], days: RangeInclusive<u32>) -> u32 {
+    registry
+        .iter()
+        .rev()
+        .find(|entry| days.contains(&entry.day))
+        .or(registry.last())
+        .map_or(0, |entry| entry.year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_year() {
+        let entry = |year, day| Entry {
+            year,
+            day,
+            program: &y2017::day1::Instance,
+        };
+        let registry = [entry(2017, 1), entry(2017, 5), entry(2018, 1)];
+
+        assert_eq!(year_registering(&registry, 1..=1), 2018);
+        assert_eq!(year_registering(&registry, 5..=5), 2017);
+        assert_eq!(year_registering(&registry, 4..=6), 2017);
+        assert_eq!(year_registering(&registry, 7..=7), 2018);
+        assert_eq!(year_registering(&[], 1..=1), 0);
+    }
+}
@@ -0,0 +1,578 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::{Index, IndexMut, Range},
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use crate::days::day::{Cancel, Error, ParseError, CHECK_EVERY};
+
+pub type Value = i64;
+
+/// Instructions run between checks for cancellation.
+pub const SLICE: u64 = CHECK_EVERY as u64;
+
+/// One of the registers `a` to `z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Register(u8);
+
+impl Register {
+    pub fn named(name: char) -> Option<Register> {
+        name.is_ascii_lowercase()
+            .then(|| Register(name as u8 - b'a'))
+    }
+
+    pub fn name(self) -> char {
+        (b'a' + self.0) as char
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Value(Value),
+    Register(Register),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Value(value) => write!(f, "{}", value),
+            Operand::Register(register) => write!(f, "{}", register),
+        }
+    }
+}
+
+/// The register file every instruction set works on, all registers starting at 0.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Registers([Value; 26]);
+
+impl Registers {
+    pub fn resolve(&self, operand: Operand) -> Value {
+        match operand {
+            Operand::Value(value) => value,
+            Operand::Register(register) => self[register],
+        }
+    }
+
+    /// Registers with their values, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (Register, Value)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Register(i as u8), *value))
+    }
+}
+
+impl Index<Register> for Registers {
+    type Output = Value;
+
+    fn index(&self, register: Register) -> &Value {
+        &self.0[register.index()]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, register: Register) -> &mut Value {
+        &mut self.0[register.index()]
+    }
+}
+
+/// The registers of the trial multiplication loops some programs look for factors
+/// with: `flag` is cleared when `factor * counter` equals `number`, with `scratch`
+/// holding the comparisons. Interpreted and compiled superinstructions both run the
+/// loops at once through these methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Factors {
+    pub flag: Register,
+    pub factor: Register,
+    pub counter: Register,
+    pub number: Register,
+    pub scratch: Register,
+}
+
+impl Factors {
+    /// Runs the loop counting `counter` up to `number` and returns the multiplications
+    /// it would have executed, or `None` if it would never end and has to run as written.
+    pub fn divides(self, registers: &mut Registers) -> Option<u64> {
+        let (factor, counter, number) = (
+            registers[self.factor],
+            registers[self.counter],
+            registers[self.number],
+        );
+        // Other starting points never reach `number`.
+        if !(1..number).contains(&counter) {
+            return None;
+        }
+        if factor > 0 && number % factor == 0 && (counter..number).contains(&(number / factor)) {
+            registers[self.flag] = 0;
+        }
+        registers[self.counter] = number;
+        registers[self.scratch] = 0;
+        Some((number - counter) as u64)
+    }
+
+    /// Runs the loop counting `factor` up to `number` around [`Factors::divides`],
+    /// starting `counter` from `start` each time, like [`Factors::divides`].
+    pub fn is_composite(self, registers: &mut Registers, start: Operand) -> Option<u64> {
+        let (factor, counter, number) = (
+            registers[self.factor],
+            registers.resolve(start),
+            registers[self.number],
+        );
+        if !(1..number).contains(&factor) || !(1..number).contains(&counter) {
+            return None;
+        }
+        // A count that overflows falls back to running the loop.
+        let muls = (number - factor).checked_mul(number - counter)?;
+        if has_factors(number, factor..number, counter..number) {
+            registers[self.flag] = 0;
+        }
+        registers[self.factor] = number;
+        registers[self.counter] = number;
+        registers[self.scratch] = 0;
+        Some(muls as u64)
+    }
+}
+
+/// Whether positive `number` is the product of a number in `a` and one in `b`.
+fn has_factors(number: Value, a: Range<Value>, b: Range<Value>) -> bool {
+    (1..)
+        .take_while(|k| k * k <= number)
+        .filter(|k| number % k == 0)
+        .any(|k| {
+            let (j, k) = (number / k, k);
+            (a.contains(&k) && b.contains(&j)) || (a.contains(&j) && b.contains(&k))
+        })
+}
+
+/// Where to continue after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jump by this offset from the current instruction.
+    Jump(Value),
+    /// Stay on this instruction and stop running, e.g. waiting for input.
+    Block,
+}
+
+/// An assembly dialect the [`Machine`] can run. Instructions display the way they
+/// are written.
+pub trait InstructionSet: FromStr<Err = ParseError> + fmt::Display {
+    /// Registers the dialect may name, counting from `a`.
+    const REGISTERS: usize = 26;
+
+    /// Whatever the dialect keeps besides registers, such as queues or counters.
+    /// Its display is shown in traces and the debugger.
+    type State: Default + fmt::Display;
+
+    fn execute(&self, registers: &mut Registers, state: &mut Self::State) -> Flow;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Blocked,
+    /// The program counter left the program.
+    Halted,
+}
+
+/// Runs a program written in instruction set `I`.
+pub struct Machine<I: InstructionSet> {
+    pub registers: Registers,
+    pub state: I::State,
+    program: Vec<I>,
+    pc: usize,
+    steps: u64,
+    trace: Option<(Trace, String)>,
+}
+
+impl<I: InstructionSet> Machine<I> {
+    pub fn new(program: Vec<I>) -> Machine<I> {
+        Machine {
+            registers: Registers::default(),
+            state: I::State::default(),
+            program,
+            pc: 0,
+            steps: 0,
+            trace: None,
+        }
+    }
+
+    /// Records every instruction executed from now on in `trace`, if given, labelled `name`.
+    pub fn trace(&mut self, trace: Option<&Trace>, name: &str) {
+        self.trace = trace.map(|trace| (trace.clone(), name.to_owned()));
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// Index of the next instruction to execute.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Executes the instruction at the program counter.
+    pub fn step(&mut self) -> Status {
+        let pc = self.pc;
+        let Some(instruction) = self.program.get(pc) else {
+            return Status::Halted;
+        };
+        let before = self.trace.as_ref().map(|_| self.registers.clone());
+
+        let flow = instruction.execute(&mut self.registers, &mut self.state);
+        match flow {
+            Flow::Next => self.pc += 1,
+            // Jumping before the start halts just like jumping past the end.
+            Flow::Jump(offset) => {
+                self.pc = self
+                    .pc
+                    .checked_add_signed(offset as isize)
+                    .unwrap_or(usize::MAX)
+            }
+            Flow::Block => {}
+        }
+        if flow != Flow::Block {
+            self.steps += 1;
+        }
+
+        if let (Some((trace, name)), Some(before)) = (&self.trace, before) {
+            let changes = match flow {
+                Flow::Block => " blocked".to_owned(),
+                _ => changes(&before, &self.registers),
+            };
+            trace.write(format_args!(
+                "{} #{} pc={} {} |{} | {}",
+                name, self.steps, pc, self.program[pc], changes, self.state
+            ));
+        }
+
+        if flow == Flow::Block {
+            Status::Blocked
+        } else if self.is_halted() {
+            Status::Halted
+        } else {
+            Status::Running
+        }
+    }
+
+    /// Steps until the program blocks or halts.
+    pub fn run(&mut self) -> Status {
+        loop {
+            match self.step() {
+                Status::Running => {}
+                status => return status,
+            }
+        }
+    }
+
+    /// Like [`Machine::run`], checking `cancel` every [`SLICE`] instructions.
+    pub fn run_cancellable(&mut self, cancel: &Cancel) -> Result<Status, Error> {
+        loop {
+            for _ in 0..SLICE {
+                match self.step() {
+                    Status::Running => {}
+                    status => return Ok(status),
+                }
+            }
+            cancel.check()?;
+        }
+    }
+}
+
+/// Registers that differ between `before` and `after`, as ` a: 1 -> 2`, or ` -` for none.
+fn changes(before: &Registers, after: &Registers) -> String {
+    let changes: String = before
+        .iter()
+        .zip(after.iter())
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((register, old), (_, new))| format!(" {}: {} -> {}", register, old, new))
+        .collect();
+    if changes.is_empty() {
+        " -".to_owned()
+    } else {
+        changes
+    }
+}
+
+/// Where machines write one line per executed instruction, shared between threads.
+/// Write errors are kept until [`Trace::finish`] so tracing never changes how a
+/// program runs.
+#[derive(Clone)]
+pub struct Trace {
+    output: Arc<Mutex<TraceOutput>>,
+    /// Starts every line, naming the day when several share the file.
+    label: String,
+}
+
+struct TraceOutput {
+    out: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+impl Trace {
+    pub fn new(out: impl Write + Send + 'static) -> Trace {
+        Trace {
+            output: Arc::new(Mutex::new(TraceOutput {
+                out: Box::new(out),
+                error: None,
+            })),
+            label: String::new(),
+        }
+    }
+
+    /// The same trace with its lines starting with `label`, e.g. the day writing them.
+    pub fn labelled(&self, label: &str) -> Trace {
+        Trace {
+            output: self.output.clone(),
+            label: format!("{} ", label),
+        }
+    }
+
+    pub fn create(path: &Path) -> io::Result<Trace> {
+        Ok(Trace::new(BufWriter::new(File::create(path)?)))
+    }
+
+    fn write(&self, line: fmt::Arguments) {
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        if output.error.is_none() {
+            if let Err(e) = writeln!(output.out, "{}{}", self.label, line) {
+                output.error = Some(e);
+            }
+        }
+    }
+
+    /// Flushes the trace, reporting the first write that failed.
+    pub fn finish(&self) -> io::Result<()> {
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        match output.error.take() {
+            Some(e) => Err(e),
+            None => output.out.flush(),
+        }
+    }
+}
+
+/// What one [`Debuggee::step`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stepped {
+    pub status: Status,
+    /// The instruction that ran, if one did.
+    pub executed: Option<Executed>,
+}
+
+/// Where an instruction ran: in which of the machines, at which pc, and where that
+/// machine continues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Executed {
+    pub machine: usize,
+    pub pc: usize,
+    pub next: usize,
+}
+
+/// A program the `debug` subcommand can step through, made of one or more machines.
+pub trait Debuggee: Send {
+    /// Executes one instruction of the machine that runs next.
+    fn step(&mut self) -> Stepped;
+
+    /// The machine that runs next, named when there are several.
+    fn current(&self) -> Option<String>;
+
+    /// The instructions of the current machine, as written.
+    fn listing(&self) -> Vec<String>;
+
+    fn pc(&self) -> usize;
+
+    fn registers(&self) -> &Registers;
+
+    /// Registers the program may name, counting from `a`.
+    fn register_count(&self) -> usize;
+
+    /// The current machine's state besides its registers.
+    fn state(&self) -> String;
+}
+
+impl<I: InstructionSet + Send> Debuggee for Machine<I>
+where
+    I::State: Send,
+{
+    fn step(&mut self) -> Stepped {
+        let pc = self.pc;
+        let status = Machine::step(self);
+        // Blocking leaves the instruction to run again later, and a halted machine
+        // has nothing left to run.
+        let ran = pc < self.program.len() && status != Status::Blocked;
+        Stepped {
+            status,
+            executed: ran.then_some(Executed {
+                machine: 0,
+                pc,
+                next: self.pc,
+            }),
+        }
+    }
+
+    fn current(&self) -> Option<String> {
+        None
+    }
+
+    fn listing(&self) -> Vec<String> {
+        self.program.iter().map(I::to_string).collect()
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    fn register_count(&self) -> usize {
+        I::REGISTERS
+    }
+
+    fn state(&self) -> String {
+        self.state.to_string()
+    }
+}
+
+/// Parses the register operand `token` of `line` for instruction set `I`.
+pub fn register<I: InstructionSet>(
+    line: &str,
+    token: Option<&str>,
+) -> Result<Register, ParseError> {
+    let token = token.ok_or_else(|| ParseError::new("missing register").at_end(line))?;
+    parse_register::<I>(token).map_err(|e| e.within(line, token))
+}
+
+/// Parses the value or register operand `token` of `line` for instruction set `I`.
+pub fn operand<I: InstructionSet>(line: &str, token: Option<&str>) -> Result<Operand, ParseError> {
+    let token = token.ok_or_else(|| ParseError::new("missing value").at_end(line))?;
+    match token.parse() {
+        Ok(value) => Ok(Operand::Value(value)),
+        Err(_) => parse_register::<I>(token)
+            .map(Operand::Register)
+            .map_err(|e| e.within(line, token)),
+    }
+}
+
+fn parse_register<I: InstructionSet>(s: &str) -> Result<Register, ParseError> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(name), None) => Register::named(name),
+        _ => None,
+    }
+    .filter(|register| register.index() < I::REGISTERS)
+    .ok_or_else(|| ParseError::new(format!("invalid register: {}", s)).spanning(s, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day::parse_lines, y2017::day23};
+
+    #[test]
+    fn run_until_halted() {
+        let lines = ["set a 3", "sub a 1", "jnz a -1", "jnz 1 -10"].map(str::to_owned);
+        let mut machine = Machine::<day23::Instruction>::new(parse_lines(&lines).unwrap());
+
+        assert_eq!(machine.step(), Status::Running);
+        assert_eq!(machine.pc(), 1);
+        assert_eq!(machine.run(), Status::Halted);
+        assert_eq!(machine.registers[Register::named('a').unwrap()], 0);
+        assert_eq!(machine.step(), Status::Halted);
+
+        let error = "set i 1".parse::<day23::Instruction>().unwrap_err();
+        assert_eq!(error.message, "invalid register: i");
+        assert_eq!(error.span, Some(4..5));
+    }
+
+    /// Collects what a trace writes.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_lines() {
+        let buffer = Buffer::default();
+        let trace = Trace::new(buffer.clone()).labelled("2017/23");
+        let lines = ["set a 3", "mul a a", "jnz 0 5"].map(str::to_owned);
+        let mut machine = Machine::<day23::Instruction>::new(parse_lines(&lines).unwrap());
+        machine.trace(Some(&trace), "part 1");
+
+        assert_eq!(machine.run(), Status::Halted);
+        trace.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap(),
+            "2017/23 part 1 #1 pc=0 set a 3 | a: 0 -> 3 | mul count 0
+2017/23 part 1 #2 pc=1 mul a a | a: 3 -> 9 | mul count 1
+2017/23 part 1 #3 pc=2 jnz 0 5 | - | mul count 1
+"
+        );
+    }
+
+    #[test]
+    fn factors() {
+        let [flag, factor, counter, number, scratch] =
+            ['f', 'd', 'e', 'b', 'g'].map(|name| Register::named(name).unwrap());
+        let factors = Factors {
+            flag,
+            factor,
+            counter,
+            number,
+            scratch,
+        };
+        let mut registers = Registers::default();
+        registers[flag] = 1;
+        registers[factor] = 2;
+        registers[number] = 15;
+        assert_eq!(
+            factors.is_composite(&mut registers, Operand::Value(2)),
+            Some(13 * 13)
+        );
+        assert_eq!(registers[flag], 0);
+        assert_eq!(registers[factor], 15);
+
+        // The count of a loop this long overflows, so it stays a loop.
+        registers[flag] = 1;
+        registers[factor] = 2;
+        registers[number] = Value::MAX / 2;
+        let before = registers.clone();
+        assert_eq!(
+            factors.is_composite(&mut registers, Operand::Value(2)),
+            None
+        );
+        assert_eq!(registers, before);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<u32>;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("1122", "3"),
+        Example::part_one("1111", "4"),
+        Example::part_one("1234", "0"),
+        Example::part_one("91212129", "9"),
+        Example::part_two("1212", "6"),
+        Example::part_two("1221", "0"),
+        Example::part_two("123425", "4"),
+        Example::part_two("123123", "12"),
+        Example::part_two("12131415", "4"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse(lines)
+    }
+
+    fn part1(&self, numbers: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(add_if_match(numbers, 1).to_string())
+    }
+
+    fn part2(&self, numbers: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(add_if_match(numbers, numbers.len() / 2).to_string()))
+    }
+}
+
+fn parse(lines: &[String]) -> Result<Vec<u32>, ParseError> {
+    lines
+        .first()
+        .ok_or("empty lines")?
+        .char_indices()
+        .map(|(i, d)| {
+            d.to_digit(10).ok_or(
+                ParseError::new(format!("bad digit {}", d))
+                    .on_line(1)
+                    .at(i..i + d.len_utf8()),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+fn add_if_match(numbers: &[u32], offset: usize) -> u32 {
+    (numbers)
+        .iter()
+        .enumerate()
+        .filter(|(a, b)| numbers[(*a + offset) % numbers.len()] == **b)
+        .map(|t| t.1)
+        .sum::<u32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_numbers() {
+        let lines = vec!["1234".to_string()];
+        assert_eq!(parse(&lines).unwrap(), vec![1, 2, 3, 4])
+    }
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
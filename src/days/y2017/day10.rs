@@ -0,0 +1,49 @@
+use super::knot;
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = String;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_two("AoC 2017", "33efeb34ea91902bb2f59c9920caa6cd"),
+        Example::part_two("1,2,3", "3efbe78a8d82f29979031a4aa0b16a9d"),
+        Example::part_two("1,2,4", "63960835bcdc130f0b66d7ff4f6a5a8e"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines.first().cloned().ok_or("empty lines".into())
+    }
+
+    fn part1(&self, line: &Self::Input, _context: &Context) -> Result<String, Error> {
+        let lengths: Vec<usize> = line
+            .split(",")
+            .map(|s| {
+                s.parse::<usize>().map_err(|e| {
+                    ParseError::new(format!("failed to parse {}: {}", s, e))
+                        .on_line(1)
+                        .spanning(line, s)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut ring = knot::Ring::new(255);
+        ring.encode(&lengths);
+
+        Ok(ring.first_two().to_string())
+    }
+
+    fn part2(&self, line: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(knot::knot_hash(line).dense_hash()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,125 @@
+use std::str::FromStr;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Direction>;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("ne,ne,ne", "3"),
+        Example::new("ne,ne,sw,sw", "0", "2"),
+        Example::part_one("ne,ne,s,s", "2"),
+        Example::part_one("se,sw,se,sw,sw", "3"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        let line = lines.first().ok_or("empty lines")?;
+        line.split(",")
+            .map(|l| {
+                l.parse::<Direction>()
+                    .map_err(|e| e.on_line(1).within(line, l))
+            })
+            .collect()
+    }
+
+    fn part1(&self, directions: &Self::Input, _context: &Context) -> Result<String, Error> {
+        let (distance, _) = walk(directions);
+        Ok(distance.to_string())
+    }
+
+    fn part2(&self, directions: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        let (_, max_distance) = walk(directions);
+        Ok(Some(max_distance.to_string()))
+    }
+}
+
+fn walk(directions: &[Direction]) -> (i32, i32) {
+    let mut grid = HexGrid::new();
+    let mut max_distance = 0;
+    for dir in directions {
+        grid.move_dir(dir);
+        if grid.distance() > max_distance {
+            max_distance = grid.distance();
+        }
+    }
+
+    (grid.distance(), max_distance)
+}
+
+struct HexGrid {
+    x: i32,
+    y: i32,
+}
+
+pub enum Direction {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexGrid {
+    fn new() -> HexGrid {
+        HexGrid { x: 0, y: 0 }
+    }
+
+    fn move_dir(&mut self, dir: &Direction) {
+        match dir {
+            Direction::N => {
+                self.y += 2;
+            }
+            Direction::NE => {
+                self.x += 1;
+                self.y += 1;
+            }
+            Direction::SE => {
+                self.x += 1;
+                self.y -= 1;
+            }
+            Direction::S => {
+                self.y -= 2;
+            }
+            Direction::SW => {
+                self.x -= 1;
+                self.y -= 1;
+            }
+            Direction::NW => {
+                self.x -= 1;
+                self.y += 1;
+            }
+        }
+    }
+
+    fn distance(&self) -> i32 {
+        (self.x.abs() + self.y.abs()) / 2
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(Direction::N),
+            "ne" => Ok(Direction::NE),
+            "s" => Ok(Direction::S),
+            "se" => Ok(Direction::SE),
+            "nw" => Ok(Direction::NW),
+            "sw" => Ok(Direction::SW),
+            _ => Err(format!("unknown direction {}", s).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Pipes;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "0 <-> 2
+1 <-> 1
+2 <-> 0, 3, 4
+3 <-> 2, 4
+4 <-> 2, 3, 6
+5 <-> 6
+6 <-> 4, 5",
+        "6",
+        "2",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, pipes: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(pipes.group(0).len().to_string())
+    }
+
+    fn part2(&self, pipes: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(pipes.groups().to_string()))
+    }
+}
+
+fn parse_input(input: &[String]) -> Result<Pipes, ParseError> {
+    let mut pipes = Pipes::new();
+    for (i, line) in input.iter().enumerate() {
+        let error = |message: String, token| {
+            ParseError::new(message)
+                .on_line(i + 1)
+                .spanning(line, token)
+        };
+        let parts = line.split(" <-> ").collect_vec();
+        if parts.len() != 2 {
+            return Err(error(format!("invalid line: {}", line), line));
+        }
+        let from = parts[0].parse::<usize>().map_err(|e| {
+            error(
+                format!("number parse failure {}: {}", parts[0], e),
+                parts[0],
+            )
+        })?;
+        let tos: Vec<_> = parts[1]
+            .split(", ")
+            .map(|s| {
+                s.parse()
+                    .map_err(|e| error(format!("number parse failure {}: {}", s, e), s))
+            })
+            .collect::<Result<_, _>>()?;
+        for to in tos {
+            pipes.add_pipe(from, to);
+        }
+    }
+    Ok(pipes)
+}
+
+pub struct Pipes {
+    pipes: HashMap<usize, Vec<usize>>,
+}
+
+impl Pipes {
+    fn new() -> Pipes {
+        Pipes {
+            pipes: HashMap::new(),
+        }
+    }
+
+    fn add_pipe(&mut self, from: usize, to: usize) {
+        self.pipes.entry(from).or_default().push(to);
+    }
+
+    fn group(&self, start: usize) -> HashSet<usize> {
+        let mut search = vec![start];
+        let mut seen = HashSet::new();
+        seen.insert(start);
+        while let Some(current) = search.pop() {
+            for &next in self.pipes.get(&current).unwrap_or(&Vec::new()) {
+                if !seen.contains(&next) {
+                    search.push(next);
+                    seen.insert(next);
+                }
+            }
+        }
+        seen
+    }
+
+    fn groups(&self) -> usize {
+        let mut groups = 0;
+        let mut seen = HashSet::new();
+        for &from in self.pipes.keys() {
+            if !seen.contains(&from) {
+                groups += 1;
+                seen.extend(self.group(from));
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
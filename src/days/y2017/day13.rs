@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Firewall;
+    const SLOW: bool = true;
+    const EXAMPLES: &'static [Example] = &[Example::new("0: 3\n1: 2\n4: 4\n6: 4", "24", "10")];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse(lines)
+    }
+
+    fn part1(&self, firewall: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(firewall.severity().to_string())
+    }
+
+    fn part2(&self, firewall: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(firewall.delay(&context.cancel)?.to_string()))
+    }
+}
+
+fn parse(input: &[String]) -> Result<Firewall, ParseError> {
+    let mut firewall = Firewall::new();
+    for (i, line) in input.iter().enumerate() {
+        let error = |message: String, token| {
+            ParseError::new(message)
+                .on_line(i + 1)
+                .spanning(line, token)
+        };
+        let (depth, range) = line
+            .split_once(": ")
+            .ok_or_else(|| error(format!("missing ': ' in {}", line), line))?;
+        let depth = depth
+            .parse::<usize>()
+            .map_err(|e| error(format!("bad depth {}: {}", line, e), depth))?;
+        let range = range
+            .parse::<usize>()
+            .map_err(|e| error(format!("bad range {}: {}", line, e), range))?;
+        firewall.add_layer(depth, range);
+    }
+    Ok(firewall)
+}
+
+pub struct Firewall {
+    depth_range: HashMap<usize, usize>,
+}
+
+impl Firewall {
+    fn new() -> Self {
+        Firewall {
+            depth_range: HashMap::new(),
+        }
+    }
+
+    fn add_layer(&mut self, depth: usize, range: usize) {
+        self.depth_range.insert(depth, range);
+    }
+
+    fn severity(&self) -> usize {
+        let mut severity = 0;
+        for (depth, range) in &self.depth_range {
+            if depth % (2 * (range - 1)) == 0 {
+                severity += depth * range;
+            }
+        }
+        severity
+    }
+
+    /// Never finishes if no delay gets through, so it checks `cancel` as it goes.
+    fn delay(&self, cancel: &Cancel) -> Result<usize, Error> {
+        let mut delay = 0;
+        loop {
+            if self
+                .depth_range
+                .iter()
+                .all(|(depth, range)| (depth + delay) % (2 * (range - 1)) != 0)
+            {
+                return Ok(delay);
+            }
+            delay += 1;
+            cancel.check()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+
+    #[test]
+    fn cancel_unsolvable() {
+        let firewall = parse(&["0: 2".to_owned(), "1: 2".to_owned()]).unwrap();
+        let cancel = Cancel::default();
+        cancel.cancel();
+        assert!(matches!(firewall.delay(&cancel), Err(Error::Cancelled)));
+    }
+}
@@ -1,24 +1,29 @@
 use itertools::Itertools;
 
-use super::{day::*, knot};
+use super::knot;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
+    type Input = Grid;
+    const EXAMPLES: &'static [Example] = &[Example::new("flqrgnkx", "8108", "1242")];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
         let line = lines.first().ok_or("empty lines".to_string())?;
-        let grid = make_grid(&line);
+        Ok(make_grid(line))
+    }
+
+    fn part1(&self, grid: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(grid.count_used().to_string())
+    }
 
-        let part1 = grid.count_used().to_string();
-        let part2 = grid.count_regions().to_string();
-        Ok(DayResult {
-            part1,
-            part2: Some(part2),
-        })
+    fn part2(&self, grid: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(grid.count_regions().to_string()))
     }
 }
 
-struct Grid {
+pub struct Grid {
     grid: Vec<Vec<bool>>,
 }
 
@@ -67,9 +72,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_grid() {
-        let grid = make_grid("flqrgnkx");
-        assert_eq!(grid.count_used(), 8108);
-        assert_eq!(grid.count_regions(), 1242);
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
@@ -0,0 +1,147 @@
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = (Generator, Generator);
+    const SLOW: bool = true;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "Generator A starts with 65\nGenerator B starts with 8921",
+        "588",
+        "309",
+    )];
+    const PARAMS: &'static [Param] = &[
+        Param::new(
+            "part1_pairs",
+            40_000_000,
+            "Pairs the judge compares in part 1",
+        ),
+        Param::new(
+            "part2_pairs",
+            5_000_000,
+            "Pairs the judge compares in part 2",
+        ),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse(lines)
+    }
+
+    fn part1(&self, generators: &Self::Input, context: &Context) -> Result<String, Error> {
+        let pairs = context.param("part1_pairs")?;
+        Ok(part1(generators.clone(), pairs, &context.cancel)?.to_string())
+    }
+
+    fn part2(&self, generators: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        let pairs = context.param("part2_pairs")?;
+        Ok(Some(
+            part2(generators.clone(), pairs, &context.cancel)?.to_string(),
+        ))
+    }
+}
+
+fn parse(input: &[String]) -> Result<(Generator, Generator), ParseError> {
+    if input.len() != 2 {
+        return Err("expected two lines".into());
+    }
+
+    let a = parse_seed(&input[0]).map_err(|e| e.on_line(1))?;
+    let b = parse_seed(&input[1]).map_err(|e| e.on_line(2))?;
+
+    Ok((Generator::new_a(a), Generator::new_b(b)))
+}
+
+fn parse_seed(line: &str) -> Result<u64, ParseError> {
+    let number = line
+        .split("starts with ")
+        .nth(1)
+        .ok_or_else(|| ParseError::new("no number").at_end(line))?;
+    number.parse::<u64>().map_err(|e| {
+        ParseError::new(format!("failed to parse number: {}", e)).spanning(line, number)
+    })
+}
+
+fn part1(
+    mut generators: (Generator, Generator),
+    pairs: usize,
+    cancel: &Cancel,
+) -> Result<usize, Error> {
+    let mut count = 0;
+    for i in 0..pairs {
+        let (a, b) = (generators.0.next(), generators.1.next());
+        if a as u16 == b as u16 {
+            count += 1;
+        }
+        if i % CHECK_EVERY == 0 {
+            cancel.check()?;
+        }
+    }
+    Ok(count)
+}
+
+fn part2(
+    mut generators: (Generator, Generator),
+    pairs: usize,
+    cancel: &Cancel,
+) -> Result<usize, Error> {
+    let mut count = 0;
+    for i in 0..pairs {
+        let (a, b) = (generators.0.next_filtered(), generators.1.next_filtered());
+        if a as u16 == b as u16 {
+            count += 1;
+        }
+        if i % CHECK_EVERY == 0 {
+            cancel.check()?;
+        }
+    }
+    Ok(count)
+}
+
+#[derive(Debug, Clone)]
+pub struct Generator {
+    seed: u64,
+    mult: u64,
+    filter: u64,
+}
+
+impl Generator {
+    fn new_a(seed: u64) -> Generator {
+        Generator {
+            seed,
+            mult: 16807,
+            filter: 4,
+        }
+    }
+
+    fn new_b(seed: u64) -> Generator {
+        Generator {
+            seed,
+            mult: 48271,
+            filter: 8,
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.seed = (self.seed * self.mult) % 2147483647;
+        self.seed
+    }
+
+    fn next_filtered(&mut self) -> u64 {
+        loop {
+            let next = self.next();
+            if next.is_multiple_of(self.filter) {
+                return next;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,216 @@
+use std::{fmt, str::FromStr};
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Move>;
+    const EXAMPLES: &'static [Example] = &[Example::new("s1,x3/4,pe/b", "baedc", "ceadb")
+        .with_params(&[("programs", 5), ("dances", 2)])];
+    const PARAMS: &'static [Param] = &[
+        Param::new("programs", 16, "Programs in the line, at most 26"),
+        Param::new(
+            "dances",
+            1_000_000_000,
+            "Times the dance is repeated in part 2",
+        ),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        let line = lines.first().ok_or("empty lines")?;
+
+        line.split(",")
+            .map(|s| {
+                s.parse()
+                    .map_err(|e: ParseError| e.on_line(1).within(line, s))
+            })
+            .collect()
+    }
+
+    fn part1(&self, moves: &Self::Input, context: &Context) -> Result<String, Error> {
+        let mut dance = Dance::new(programs(moves, context)?);
+        dance.dance(moves);
+        Ok(dance.to_string())
+    }
+
+    fn part2(&self, moves: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        let total = context.param("dances")?;
+        let mut dance = Dance::new(programs(moves, context)?);
+        let initial = dance.to_string();
+
+        for i in 1..=total {
+            dance.dance(moves);
+            if dance.to_string() == initial {
+                let cycle = i;
+                let remaining = total % cycle;
+                for _ in 0..remaining {
+                    dance.dance(moves);
+                }
+                break;
+            }
+        }
+        Ok(Some(dance.to_string()))
+    }
+}
+
+/// The `programs` parameter, which has to name a program for every letter and
+/// position the moves use.
+fn programs(moves: &[Move], context: &Context) -> Result<u8, Error> {
+    let programs = context.param("programs")?;
+    let needed = moves.iter().map(Move::programs).max().unwrap_or(1).max(1);
+    if !(needed..=26).contains(&programs) {
+        let message = format!(
+            "the moves need {} to 26 programs, got programs={}",
+            needed, programs
+        );
+        return Err(ParseError::new(message).on_line(1).into());
+    }
+    Ok(programs as u8)
+}
+
+#[derive(Debug)]
+pub enum Move {
+    Spin(usize),
+    Exchange(usize, usize),
+    Partner(char, char),
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let kind = chars.next().ok_or("empty string")?;
+        let rest = chars.as_str();
+        match kind {
+            's' => Ok(Move::Spin(parse_part(s, rest, "spin")?)),
+            'x' => {
+                let (a, b) = parse_pair(s, rest)?;
+                Ok(Move::Exchange(a, b))
+            }
+            'p' => {
+                let (a, b): (char, char) = parse_pair(s, rest)?;
+                if let Some(bad) = [a, b].into_iter().find(|c| !c.is_ascii_lowercase()) {
+                    let at = 1 + rest.find(bad).unwrap_or(0);
+                    return Err(ParseError::new(format!("invalid program: {}", bad))
+                        .at(at..at + bad.len_utf8()));
+                }
+                Ok(Move::Partner(a, b))
+            }
+            _ => {
+                Err(ParseError::new(format!("unknown move kind: {}", kind)).at(0..kind.len_utf8()))
+            }
+        }
+    }
+}
+
+impl Move {
+    /// The fewest programs a line needs for this move.
+    fn programs(&self) -> usize {
+        match *self {
+            Move::Spin(count) => count,
+            Move::Exchange(a, b) => a.max(b) + 1,
+            Move::Partner(a, b) => (a.max(b) as u8 - b'a') as usize + 1,
+        }
+    }
+}
+
+fn parse_pair<T: FromStr>(s: &str, rest: &str) -> Result<(T, T), ParseError>
+where
+    T::Err: fmt::Display,
+{
+    let (a, b) = rest
+        .split_once("/")
+        .ok_or_else(|| ParseError::new("missing second part").at_end(s))?;
+    Ok((
+        parse_part(s, a, "first part")?,
+        parse_part(s, b, "second part")?,
+    ))
+}
+
+fn parse_part<T: FromStr>(s: &str, part: &str, name: &str) -> Result<T, ParseError>
+where
+    T::Err: fmt::Display,
+{
+    part.parse()
+        .map_err(|e| ParseError::new(format!("failed to parse {}: {}", name, e)).spanning(s, part))
+}
+
+struct Dance {
+    programs: Vec<char>,
+}
+
+impl Dance {
+    fn new(size: u8) -> Self {
+        Dance {
+            programs: (b'a'..(b'a' + size)).map(char::from).collect(),
+        }
+    }
+
+    fn spin(&mut self, count: usize) {
+        let count = self.programs.len() - count;
+        let mut new = self.programs[count..].to_vec();
+        new.extend_from_slice(&self.programs[..count]);
+        self.programs = new;
+    }
+
+    fn exchange(&mut self, a: usize, b: usize) {
+        self.programs.swap(a, b);
+    }
+
+    fn partner(&mut self, a: char, b: char) {
+        let a = self.programs.iter().position(|&c| c == a).unwrap();
+        let b = self.programs.iter().position(|&c| c == b).unwrap();
+        self.exchange(a, b);
+    }
+
+    fn dance(&mut self, moves: &[Move]) {
+        for m in moves {
+            match m {
+                Move::Spin(count) => self.spin(*count),
+                Move::Exchange(a, b) => self.exchange(*a, *b),
+                Move::Partner(a, b) => self.partner(*a, *b),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Dance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.programs.iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+
+    #[test]
+    fn test_dance() {
+        let mut dance = Dance::new(5);
+        dance.dance(&[Move::Spin(1), Move::Exchange(3, 4), Move::Partner('e', 'b')]);
+        assert_eq!(dance.to_string(), "baedc");
+    }
+
+    #[test]
+    fn too_few_programs() {
+        let moves = Instance.parse(&["s1,x3/4,pe/b".to_owned()]).unwrap();
+        for programs in [0, 4, 27] {
+            let mut context = Context::default();
+            context.params.set("programs", programs);
+            assert!(matches!(
+                Instance.part1(&moves, &context),
+                Err(Error::Parse(_))
+            ));
+        }
+
+        let error = Instance.parse(&["s1,pA/b".to_owned()]).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: invalid program: A");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = usize;
+    const SLOW: bool = true;
+    const EXAMPLES: &'static [Example] = &[Example::new("3", "638", "1222153")];
+    const PARAMS: &'static [Param] = &[
+        Param::new(
+            "part1_insertions",
+            2017,
+            "Values the spinlock inserts in part 1",
+        ),
+        Param::new(
+            "part2_insertions",
+            50_000_000,
+            "Values the spinlock inserts in part 2",
+        ),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines
+            .first()
+            .ok_or("empty lines")?
+            .parse::<usize>()
+            .map_err(|e| ParseError::new(e.to_string()).on_line(1))
+    }
+
+    fn part1(&self, step: &Self::Input, context: &Context) -> Result<String, Error> {
+        Ok(part1(*step, context.param("part1_insertions")?).to_string())
+    }
+
+    fn part2(&self, step: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        let insertions = context.param("part2_insertions")?;
+        Ok(Some(part2(*step, insertions, &context.cancel)?.to_string()))
+    }
+}
+
+#[derive(Debug)]
+struct Spinlock {
+    buffer: Vec<usize>,
+    position: usize,
+    step: usize,
+}
+
+impl Spinlock {
+    fn new(step: usize) -> Spinlock {
+        Spinlock {
+            buffer: vec![0],
+            position: 0,
+            step,
+        }
+    }
+
+    fn insert(&mut self, value: usize) {
+        self.position = (self.position + self.step) % self.buffer.len() + 1;
+        self.buffer.insert(self.position, value);
+    }
+
+    fn value_after(&self, value: usize) -> usize {
+        let position = self.buffer.iter().position(|&v| v == value).unwrap();
+        self.buffer[(position + 1) % self.buffer.len()]
+    }
+}
+
+fn part1(step: usize, insertions: usize) -> usize {
+    let mut spinlock = Spinlock::new(step);
+    for i in 1..=insertions {
+        spinlock.insert(i);
+    }
+    spinlock.value_after(insertions)
+}
+
+fn part2(step: usize, insertions: usize, cancel: &Cancel) -> Result<usize, Error> {
+    let mut position = 0;
+    let mut value = 0;
+    for i in 1..=insertions {
+        position = (position + step) % i + 1;
+        if position == 1 {
+            value = i;
+        }
+        if i % CHECK_EVERY == 0 {
+            cancel.check()?;
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,424 @@
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+use crate::days::{
+    bytecode::{self, Bytecode, Event, Lower, Lowering, Processor, Test},
+    day::*,
+    vm::{
+        self, Debuggee, Executed, Flow, InstructionSet, Machine, Operand, Register, Registers,
+        Status, Stepped, Trace, Value,
+    },
+};
+
+pub struct Instance;
+
+const SOUND: &str = "set a 1
+add a 2
+mul a a
+mod a 5
+snd a
+set a 0
+rcv a
+jgz a -1
+set a 1
+jgz a -2";
+
+const DUET: &str = "snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d";
+
+impl Day for Instance {
+    type Input = Vec<Instruction>;
+    const PARAMS: &'static [Param] = &[bytecode::PARAM];
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one(SOUND, "4"),
+        Example::part_two(DUET, "3"),
+        Example::part_one(SOUND, "4").with_params(&[("bytecode", 0)]),
+        Example::part_two(DUET, "3").with_params(&[("bytecode", 0)]),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1(&self, instructions: &Self::Input, context: &Context) -> Result<String, Error> {
+        if bytecode::enabled(context)? {
+            return Ok(play(&bytecode::compile(instructions), &context.cancel)?.to_string());
+        }
+
+        let mut duet = Duet::new(instructions.clone());
+        duet.state.sound = true;
+        duet.trace(context.trace.as_ref(), "part 1");
+
+        duet.run_cancellable(&context.cancel)?;
+        Ok(duet
+            .state
+            .outbox
+            .last()
+            .copied()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    fn part2(
+        &self,
+        instructions: &Self::Input,
+        context: &Context,
+    ) -> Result<Option<String>, Error> {
+        if bytecode::enabled(context)? {
+            let sent = run_compiled_duet(&bytecode::compile(instructions), &context.cancel)?;
+            return Ok(Some(sent.to_string()));
+        }
+
+        Ok(Some(
+            run_duet(
+                instructions.clone(),
+                &context.cancel,
+                context.trace.as_ref(),
+            )?
+            .to_string(),
+        ))
+    }
+
+    fn debug(&self, instructions: &Self::Input, part: Part) -> Option<Box<dyn Debuggee>> {
+        match part {
+            Part::One => {
+                let mut duet = Duet::new(instructions.clone());
+                duet.state.sound = true;
+                Some(Box::new(duet))
+            }
+            Part::Two => Some(Box::new(Pair::new(instructions.clone()))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Snd(Operand),
+    Set(Register, Operand),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Mod(Register, Operand),
+    Rcv(Register),
+    Jgz(Operand, Operand),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let kind = parts.next().ok_or("empty string")?;
+        let a = parts.next();
+        let b = parts.next();
+        let register = vm::register::<Self>;
+        let operand = vm::operand::<Self>;
+        match kind {
+            "snd" => Ok(Instruction::Snd(operand(s, a)?)),
+            "set" => Ok(Instruction::Set(register(s, a)?, operand(s, b)?)),
+            "add" => Ok(Instruction::Add(register(s, a)?, operand(s, b)?)),
+            "mul" => Ok(Instruction::Mul(register(s, a)?, operand(s, b)?)),
+            "mod" => Ok(Instruction::Mod(register(s, a)?, operand(s, b)?)),
+            "rcv" => Ok(Instruction::Rcv(register(s, a)?)),
+            "jgz" => Ok(Instruction::Jgz(operand(s, a)?, operand(s, b)?)),
+            _ => Err(ParseError::new(format!("unknown instruction: {}", kind)).spanning(s, kind)),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Snd(value) => write!(f, "snd {}", value),
+            Instruction::Set(register, value) => write!(f, "set {} {}", register, value),
+            Instruction::Add(register, value) => write!(f, "add {} {}", register, value),
+            Instruction::Mul(register, value) => write!(f, "mul {} {}", register, value),
+            Instruction::Mod(register, value) => write!(f, "mod {} {}", register, value),
+            Instruction::Rcv(register) => write!(f, "rcv {}", register),
+            Instruction::Jgz(value, offset) => write!(f, "jgz {} {}", value, offset),
+        }
+    }
+}
+
+/// How a program talks to the outside. With `sound`, `snd` plays a sound and `rcv`
+/// of a non-zero register stops the program; otherwise `snd` sends a value and `rcv`
+/// takes one from `inbox`, blocking while it's empty.
+#[derive(Debug, Default)]
+pub struct Channel {
+    pub sound: bool,
+    pub inbox: VecDeque<Value>,
+    /// Values sent or sounds played since the caller last drained it.
+    pub outbox: Vec<Value>,
+    pub sent: usize,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = |values: &mut dyn Iterator<Item = &Value>| {
+            values.map(Value::to_string).collect::<Vec<_>>().join(", ")
+        };
+        write!(
+            f,
+            "inbox [{}] outbox [{}] sent {}",
+            values(&mut self.inbox.iter()),
+            values(&mut self.outbox.iter()),
+            self.sent
+        )
+    }
+}
+
+impl InstructionSet for Instruction {
+    type State = Channel;
+
+    fn execute(&self, registers: &mut Registers, channel: &mut Channel) -> Flow {
+        match *self {
+            Instruction::Snd(value) => {
+                channel.outbox.push(registers.resolve(value));
+                channel.sent += 1;
+            }
+            Instruction::Set(register, value) => registers[register] = registers.resolve(value),
+            Instruction::Add(register, value) => registers[register] += registers.resolve(value),
+            Instruction::Mul(register, value) => registers[register] *= registers.resolve(value),
+            Instruction::Mod(register, value) => registers[register] %= registers.resolve(value),
+            Instruction::Rcv(register) => {
+                if channel.sound {
+                    if registers[register] > 0 {
+                        return Flow::Block;
+                    }
+                } else {
+                    match channel.inbox.pop_front() {
+                        Some(value) => registers[register] = value,
+                        None => return Flow::Block,
+                    }
+                }
+            }
+            Instruction::Jgz(value, offset) => {
+                if registers.resolve(value) > 0 {
+                    return Flow::Jump(registers.resolve(offset));
+                }
+            }
+        }
+        Flow::Next
+    }
+}
+
+/// Interpreter for the sound/duet assembly; `run` stops at a blocking `rcv`.
+pub type Duet = Machine<Instruction>;
+
+impl Lower for Instruction {
+    fn lower(&self, at: &Lowering) -> bytecode::Op {
+        match *self {
+            Instruction::Snd(value) => at.send(value),
+            Instruction::Set(register, value) => at.set(register, value),
+            Instruction::Add(register, value) => at.add(register, value),
+            Instruction::Mul(register, value) => at.mul(register, value),
+            Instruction::Mod(register, value) => at.modulo(register, value),
+            Instruction::Rcv(register) => at.receive(register),
+            Instruction::Jgz(value, offset) => at.jump(Test::Positive, value, offset),
+        }
+    }
+}
+
+/// Runs two copies of the program talking to each other until both are stuck,
+/// returning how many values program 1 sent.
+pub fn run_duet(
+    instructions: Vec<Instruction>,
+    cancel: &Cancel,
+    trace: Option<&Trace>,
+) -> Result<usize, Error> {
+    let [mut duet0, mut duet1] = programs(instructions);
+    duet0.trace(trace, "part 2, program 0");
+    duet1.trace(trace, "part 2, program 1");
+
+    loop {
+        let status0 = duet0.run_cancellable(cancel)?;
+        let status1 = duet1.run_cancellable(cancel)?;
+
+        duet0.state.inbox.extend(duet1.state.outbox.drain(..));
+        duet1.state.inbox.extend(duet0.state.outbox.drain(..));
+
+        if duet0.state.inbox.is_empty() && duet1.state.inbox.is_empty() {
+            break;
+        }
+        if status0 == Status::Halted && status1 == Status::Halted {
+            break;
+        }
+        cancel.check()?;
+    }
+
+    Ok(duet1.state.sent)
+}
+
+/// Plays the compiled program as sounds, returning the one recovered by the first
+/// `rcv` of a positive register, or the last one played if it halts first.
+fn play(code: &Bytecode, cancel: &Cancel) -> Result<Value, Error> {
+    let mut processor = Processor::new(code);
+    let mut sound = 0;
+    loop {
+        match processor.run(bytecode::SLICE) {
+            Event::Send(value) => sound = value,
+            Event::Receive(register) if processor.registers[register] > 0 => return Ok(sound),
+            Event::Receive(_) => processor.skip(),
+            Event::Paused => cancel.check()?,
+            Event::Halted => return Ok(sound),
+        }
+    }
+}
+
+/// [`run_duet`] on the compiled program.
+pub fn run_compiled_duet(code: &Bytecode, cancel: &Cancel) -> Result<usize, Error> {
+    let p = Register::named('p').expect("p is a register");
+    let mut processors = [Processor::new(code), Processor::new(code)];
+    processors[1].registers[p] = 1;
+    let mut inboxes = [VecDeque::new(), VecDeque::new()];
+    let mut sent = [0; 2];
+    let mut halted = [false; 2];
+
+    loop {
+        for id in 0..2 {
+            // Runs until it waits for a value that hasn't been sent yet.
+            while !halted[id] {
+                match processors[id].run(bytecode::SLICE) {
+                    Event::Send(value) => {
+                        inboxes[1 - id].push_back(value);
+                        sent[id] += 1;
+                    }
+                    Event::Receive(_) => match inboxes[id].pop_front() {
+                        Some(value) => processors[id].receive(value),
+                        None => break,
+                    },
+                    Event::Paused => cancel.check()?,
+                    Event::Halted => halted[id] = true,
+                }
+            }
+        }
+
+        if (0..2).all(|id| halted[id] || inboxes[id].is_empty()) {
+            return Ok(sent[1]);
+        }
+        cancel.check()?;
+    }
+}
+
+/// Two copies of the program, with `p` set to their program ID.
+fn programs(instructions: Vec<Instruction>) -> [Duet; 2] {
+    let p = Register::named('p').expect("p is a register");
+    let mut duets = [Duet::new(instructions.clone()), Duet::new(instructions)];
+    duets[1].registers[p] = 1;
+    duets
+}
+
+/// Both programs of part 2, stepped one instruction at a time for the debugger. The
+/// current program keeps running until it can't, then the other one takes over.
+struct Pair {
+    duets: [Duet; 2],
+    current: usize,
+}
+
+impl Pair {
+    fn new(instructions: Vec<Instruction>) -> Pair {
+        Pair {
+            duets: programs(instructions),
+            current: 0,
+        }
+    }
+
+    /// Hands over to the other program if only that one can run.
+    fn switch_if_stuck(&mut self) {
+        let other = 1 - self.current;
+        if !Pair::can_run(&self.duets[self.current]) && Pair::can_run(&self.duets[other]) {
+            self.current = other;
+        }
+    }
+
+    fn can_run(duet: &Duet) -> bool {
+        match duet.program().get(duet.pc()) {
+            Some(Instruction::Rcv(_)) => !duet.state.inbox.is_empty(),
+            Some(_) => true,
+            None => false,
+        }
+    }
+}
+
+impl Debuggee for Pair {
+    fn step(&mut self) -> Stepped {
+        self.switch_if_stuck();
+        let machine = self.current;
+        let executed = Debuggee::step(&mut self.duets[machine])
+            .executed
+            .map(|executed| Executed {
+                machine,
+                ..executed
+            });
+
+        let [duet0, duet1] = &mut self.duets;
+        duet0.state.inbox.extend(duet1.state.outbox.drain(..));
+        duet1.state.inbox.extend(duet0.state.outbox.drain(..));
+        // Show the program that runs next, not one that has just halted or blocked.
+        self.switch_if_stuck();
+
+        let status = if self.duets.iter().any(Pair::can_run) {
+            Status::Running
+        } else if self.duets.iter().all(Duet::is_halted) {
+            Status::Halted
+        } else {
+            Status::Blocked
+        };
+        Stepped { status, executed }
+    }
+
+    fn current(&self) -> Option<String> {
+        Some(format!("program {}", self.current))
+    }
+
+    fn listing(&self) -> Vec<String> {
+        self.duets[self.current].listing()
+    }
+
+    fn pc(&self) -> usize {
+        self.duets[self.current].pc()
+    }
+
+    fn registers(&self) -> &Registers {
+        &self.duets[self.current].registers
+    }
+
+    fn register_count(&self) -> usize {
+        self.duets[self.current].register_count()
+    }
+
+    fn state(&self) -> String {
+        self.duets[self.current].state.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+
+    #[test]
+    fn pair_after_halt() {
+        let lines = ["jgz p 2", "jgz 1 5", "set a 1", "set a 2"].map(str::to_owned);
+        let mut pair = Pair::new(parse_lines(&lines).unwrap());
+
+        assert_eq!(pair.step().status, Status::Running);
+        // Program 0 jumps out of the program, so program 1 runs next.
+        assert_eq!(pair.step().status, Status::Running);
+        assert_eq!(pair.current().unwrap(), "program 1");
+        assert_eq!(pair.pc(), 0);
+        assert_eq!(pair.step().status, Status::Running);
+        assert_eq!(pair.step().status, Status::Running);
+        let stepped = pair.step();
+        assert_eq!(stepped.status, Status::Halted);
+        assert_eq!(
+            stepped.executed,
+            Some(Executed {
+                machine: 1,
+                pc: 3,
+                next: 4
+            })
+        );
+    }
+}
@@ -1,18 +1,35 @@
 use std::collections::HashMap;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let line = parse(&lines);
-
-        let (part1, part2) = line.traverse();
-        Ok(DayResult {
-            part1,
-            part2: Some(part2.to_string()),
-        })
+    type Input = Line;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "     |          
+     |  +--+    
+     A  |  C    
+ F---|----E|--+ 
+     |  |  |  D 
+     +B-+  +--+ 
+",
+        "ABCDEF",
+        "38",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Ok(parse(lines))
+    }
+
+    fn part1(&self, line: &Self::Input, _context: &Context) -> Result<String, Error> {
+        let (letters, _) = line.traverse();
+        Ok(letters)
+    }
+
+    fn part2(&self, line: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        let (_, steps) = line.traverse();
+        Ok(Some(steps.to_string()))
     }
 }
 
@@ -49,9 +66,9 @@ impl Direction {
     fn turn(&self, sections: &HashMap<Coord, Section>, coord: &Coord) -> Option<Direction> {
         let left = self.turn_left();
         let right = self.turn_right();
-        if sections.get(&left.move_coord(&coord)).is_some() {
+        if sections.get(&left.move_coord(coord)).is_some() {
             Some(left)
-        } else if sections.get(&right.move_coord(&coord)).is_some() {
+        } else if sections.get(&right.move_coord(coord)).is_some() {
             Some(right)
         } else {
             None
@@ -89,7 +106,7 @@ enum Section {
     Label(char),
 }
 
-struct Line {
+pub struct Line {
     start: Coord,
     sections: HashMap<Coord, Section>,
 }
@@ -171,24 +188,10 @@ fn parse(input: &[String]) -> Line {
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
 
     #[test]
-    fn example() {
-        let input = "     |          
-     |  +--+    
-     A  |  C    
- F---|----E|--+ 
-     |  |  |  D 
-     +B-+  +--+ 
-"
-        .lines()
-        .map(|s| s.to_string())
-        .collect_vec();
-
-        let line = parse(&input);
-        assert_eq!(line.traverse(), ("ABCDEF".to_string(), 38));
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
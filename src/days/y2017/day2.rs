@@ -0,0 +1,80 @@
+use itertools::Itertools;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Vec<u32>>;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("5 1 9 5\n7 5 3\n2 4 6 8", "18"),
+        Example::part_two("5 9 2 8\n9 4 7 3\n3 8 6 5", "9"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse(lines)
+    }
+
+    fn part1(&self, parsed: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(checksum(parsed).to_string())
+    }
+
+    fn part2(&self, parsed: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(checksum_divisible(parsed).to_string()))
+    }
+}
+
+fn parse(lines: &[String]) -> Result<Vec<Vec<u32>>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            l.split_whitespace()
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<u32>().map_err(|e| {
+                        ParseError::new(format!("parse failure ({}): {}", s, e))
+                            .on_line(i + 1)
+                            .spanning(l, s)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn checksum(nums: &[Vec<u32>]) -> u32 {
+    nums.iter()
+        .map(|l| l.iter().max().unwrap_or(&0) - l.iter().min().to_owned().unwrap_or(&0))
+        .sum()
+}
+
+fn checksum_divisible(nums: &[Vec<u32>]) -> u32 {
+    nums.iter()
+        .map(|l| {
+            l.iter()
+                .tuple_combinations::<(_, _)>()
+                .map(|(a, b)| (*a.max(b), *a.min(b)))
+                .find(|(a, b)| a % b == 0)
+                .map_or(0, |(a, b)| a / b)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_numbers() {
+        assert_eq!(
+            parse(&["1  2 3  4".to_string(), "5 6 7".to_string()]).unwrap(),
+            vec![vec![1, 2, 3, 4], vec![5, 6, 7]]
+        )
+    }
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
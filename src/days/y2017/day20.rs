@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Particle>;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one(
+            "p=<3,0,0>, v=<2,0,0>, a=<-1,0,0>\np=<4,0,0>, v=<0,0,0>, a=<-2,0,0>",
+            "0",
+        ),
+        Example::part_two(
+            "p=<-6,0,0>, v=<3,0,0>, a=<0,0,0>
+p=<-4,0,0>, v=<2,0,0>, a=<0,0,0>
+p=<-2,0,0>, v=<1,0,0>, a=<0,0,0>
+p=<3,0,0>, v=<-1,0,0>, a=<0,0,0>",
+            "1",
+        ),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1(&self, particles: &Self::Input, context: &Context) -> Result<String, Error> {
+        Ok(closest(particles.clone(), &context.cancel)?.to_string())
+    }
+
+    fn part2(&self, particles: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(
+            survivors(particles.clone(), &context.cancel)?.to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Point {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+#[derive(Clone)]
+pub struct Particle {
+    position: Point,
+    velocity: Point,
+    acceleration: Point,
+    collided: bool,
+}
+
+impl Particle {
+    fn new(position: Point, velocity: Point, acceleration: Point) -> Particle {
+        Particle {
+            position,
+            velocity,
+            acceleration,
+            collided: false,
+        }
+    }
+
+    fn step(&mut self) {
+        self.velocity.x += self.acceleration.x;
+        self.velocity.y += self.acceleration.y;
+        self.velocity.z += self.acceleration.z;
+        self.position.x += self.velocity.x;
+        self.position.y += self.velocity.y;
+        self.position.z += self.velocity.z;
+    }
+
+    fn manhatten(&self) -> i64 {
+        self.position.x.abs() + self.position.y.abs() + self.position.z.abs()
+    }
+}
+
+impl FromStr for Particle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(", ");
+        let mut point = |name: &str| {
+            let part = parts
+                .next()
+                .ok_or_else(|| ParseError::new(format!("missing {}", name)).at_end(s))?;
+            part.parse::<Point>()
+                .map_err(|e: ParseError| e.within(s, part))
+        };
+        let position = point("position")?;
+        let velocity = point("velocity")?;
+        let acceleration = point("acceleration")?;
+
+        Ok(Particle::new(position, velocity, acceleration))
+    }
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s
+            .split_once("=<")
+            .and_then(|(_, rest)| rest.strip_suffix(">"))
+            .ok_or_else(|| ParseError::new(format!("expected <x,y,z>: {}", s)).spanning(s, s))?;
+        let mut parts = coordinates.split(",");
+        let mut coordinate = |name: &str| {
+            let part = parts
+                .next()
+                .ok_or_else(|| ParseError::new(format!("missing {}", name)).spanning(s, s))?;
+            part.parse::<i64>().map_err(|e| {
+                ParseError::new(format!("failed to parse {}: {}", name, e)).spanning(s, part)
+            })
+        };
+        let x = coordinate("x")?;
+        let y = coordinate("y")?;
+        let z = coordinate("z")?;
+
+        Ok(Point { x, y, z })
+    }
+}
+
+/// Index of the particle staying closest to the origin, once the same one has been
+/// closest for 1000 steps in a row.
+fn closest(mut points: Vec<Particle>, cancel: &Cancel) -> Result<usize, Error> {
+    let mut last_closest = 0;
+    let mut matched = 0;
+    while matched <= 1000 {
+        for point in &mut points {
+            point.step();
+        }
+
+        let closest = points
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, particle)| particle.manhatten())
+            .unwrap()
+            .0;
+
+        if closest == last_closest {
+            matched += 1;
+        } else {
+            matched = 0;
+            last_closest = closest;
+        }
+        cancel.check()?;
+    }
+    Ok(last_closest)
+}
+
+/// Particles left once none have collided for 1000 steps in a row.
+fn survivors(mut points: Vec<Particle>, cancel: &Cancel) -> Result<usize, Error> {
+    let mut quiet = 0;
+    while quiet <= 1000 {
+        for point in &mut points {
+            point.step();
+        }
+
+        let mut collided = false;
+        let mut remaining = points.iter_mut().filter(|p| !p.collided).collect_vec();
+        for i in 0..remaining.len() {
+            for j in i + 1..remaining.len() {
+                if remaining[i].position == remaining[j].position {
+                    remaining[i].collided = true;
+                    remaining[j].collided = true;
+                    collided = true;
+                }
+            }
+        }
+
+        quiet = if collided { 0 } else { quiet + 1 };
+        cancel.check()?;
+    }
+    Ok(points.iter().filter(|p| !p.collided).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
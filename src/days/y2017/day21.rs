@@ -0,0 +1,225 @@
+use std::{str::FromStr, vec};
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = RuleBook;
+    const SLOW: bool = true;
+    const EXAMPLES: &'static [Example] = &[Example::part_one(
+        "../.# => ##./#../...\n.#./..#/### => #..#/..../..../#..#",
+        "12",
+    )
+    .with_params(&[("part1_iterations", 2)])];
+    const PARAMS: &'static [Param] = &[
+        Param::new("part1_iterations", 5, "Times the art is enhanced in part 1"),
+        Param::new(
+            "part2_iterations",
+            18,
+            "Times the art is enhanced in part 2",
+        ),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Ok(RuleBook::new(parse_lines(lines)?))
+    }
+
+    fn part1(&self, rule_book: &Self::Input, context: &Context) -> Result<String, Error> {
+        let iterations = context.param("part1_iterations")?;
+        Ok(simulate(rule_book, iterations, &context.cancel)?.to_string())
+    }
+
+    fn part2(&self, rule_book: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        let iterations = context.param("part2_iterations")?;
+        Ok(Some(
+            simulate(rule_book, iterations, &context.cancel)?.to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Rule {
+    size: usize,
+    to: Vec<Vec<bool>>,
+    permutations: Vec<Vec<Vec<bool>>>,
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (from, to) = s
+            .split_once(" => ")
+            .ok_or_else(|| ParseError::new("missing ' => '").spanning(s, s))?;
+
+        let from = parse_pattern(s, from)?;
+        let to = parse_pattern(s, to)?;
+        if from.len() != 2 && from.len() != 3 {
+            return Err(ParseError::new("rules must match 2x2 or 3x3 squares").spanning(s, s));
+        }
+        if to.len() != from.len() + 1 {
+            return Err(ParseError::new("rules must grow the square by one").spanning(s, s));
+        }
+
+        Ok(Rule::new(from, to))
+    }
+}
+
+fn parse_pattern(line: &str, pattern: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let grid = pattern
+        .split("/")
+        .map(|row| {
+            row.char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::new(format!("invalid pixel {}", c))
+                        .spanning(line, &row[i..i + c.len_utf8()])),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if grid.iter().any(|row| row.len() != grid.len()) {
+        return Err(ParseError::new("pattern is not square").spanning(line, pattern));
+    }
+    Ok(grid)
+}
+
+impl Rule {
+    fn new(mut from: Vec<Vec<bool>>, to: Vec<Vec<bool>>) -> Rule {
+        let mut permutations = vec![];
+        for _ in 0..4 {
+            flip_ver(&mut from);
+            permutations.push(from.clone());
+            flip_ver(&mut from);
+            permutations.push(from.clone());
+            rotate(&mut from);
+            permutations.push(from.clone());
+        }
+
+        permutations.sort();
+        permutations.dedup();
+        Rule {
+            size: from.len(),
+            to,
+            permutations,
+        }
+    }
+
+    fn apply(&self, portion: &Vec<Vec<bool>>) -> Option<Vec<Vec<bool>>> {
+        let to = &self.to;
+
+        for permutation in &self.permutations {
+            if permutation == portion {
+                return Some(to.clone());
+            }
+        }
+
+        None
+    }
+}
+
+fn flip_ver(portion: &mut [Vec<bool>]) {
+    portion.reverse();
+}
+
+fn rotate(portion: &mut Vec<Vec<bool>>) {
+    let mut rotated = vec![vec![false; portion.len()]; portion.len()];
+    for (y, row) in portion.iter().enumerate() {
+        for (x, &value) in row.iter().enumerate() {
+            rotated[x][portion.len() - y - 1] = value;
+        }
+    }
+
+    *portion = rotated;
+}
+
+pub struct RuleBook {
+    two_rules: Vec<Rule>,
+    three_rules: Vec<Rule>,
+}
+
+impl RuleBook {
+    fn new(rules: Vec<Rule>) -> RuleBook {
+        let (two_rules, three_rules) = rules.into_iter().partition(|rule| rule.size == 2);
+        RuleBook {
+            two_rules,
+            three_rules,
+        }
+    }
+
+    fn apply(&self, grid: &[Vec<bool>]) -> Vec<Vec<bool>> {
+        let size = if grid.len().is_multiple_of(2) { 2 } else { 3 };
+        let rules = if grid.len().is_multiple_of(2) {
+            &self.two_rules
+        } else {
+            &self.three_rules
+        };
+        let new_size = size + 1;
+        let mut new_grid =
+            vec![vec![false; grid.len() / size * new_size]; grid.len() / size * new_size];
+
+        for y in 0..grid.len() / size {
+            for x in 0..grid.len() / size {
+                let mut portion = vec![vec![false; size]; size];
+                for dy in 0..size {
+                    for dx in 0..size {
+                        portion[dy][dx] = grid[y * size + dy][x * size + dx];
+                    }
+                }
+
+                for rule in rules {
+                    if let Some(to) = rule.apply(&portion) {
+                        for dy in 0..new_size {
+                            for dx in 0..new_size {
+                                new_grid[y * new_size + dy][x * new_size + dx] = to[dy][dx];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        new_grid
+    }
+}
+
+fn simulate(rule_book: &RuleBook, iterations: usize, cancel: &Cancel) -> Result<usize, Error> {
+    let mut grid = vec![
+        vec![false, true, false],
+        vec![false, false, true],
+        vec![true, true, true],
+    ];
+
+    // The grid grows with every iteration, so each one is worth a check.
+    for _ in 0..iterations {
+        grid = rule_book.apply(&grid);
+        cancel.check()?;
+    }
+
+    Ok(grid.iter().flatten().filter(|&&v| v).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+
+    #[test]
+    fn cancelled() {
+        let lines = ["../.# => ##./#../...", ".#./..#/### => #..#/..../..../#..#"];
+        let rule_book = Instance.parse(&lines.map(str::to_owned)).unwrap();
+        let cancel = Cancel::default();
+        cancel.cancel();
+        assert!(matches!(
+            simulate(&rule_book, 1_000, &cancel),
+            Err(Error::Cancelled)
+        ));
+    }
+}
@@ -1,21 +1,36 @@
 use std::{collections::HashMap, mem::swap, str::FromStr};
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let mut map = lines.join("\n").parse::<Map>()?;
-        let part1 = part1(&mut map).to_string();
+    type Input = Map;
+    const SLOW: bool = true;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("..#\n#..\n...", "41").with_params(&[("part1_bursts", 70)]),
+        Example::part_one("..#\n#..\n...", "5587"),
+        Example::part_two("..#\n#..\n...", "26").with_params(&[("part2_bursts", 100)]),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param::new("part1_bursts", 10_000, "Bursts of activity in part 1"),
+        Param::new("part2_bursts", 10_000_000, "Bursts of activity in part 2"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines.join("\n").parse::<Map>()
+    }
 
-        let mut map = lines.join("\n").parse::<Map>()?;
-        let part2 = part2(&mut map).to_string();
+    fn part1(&self, map: &Self::Input, context: &Context) -> Result<String, Error> {
+        let bursts = context.param("part1_bursts")?;
+        Ok(part(&mut map.clone(), bursts, false, &context.cancel)?.to_string())
+    }
 
-        Ok(DayResult {
-            part1,
-            part2: Some(part2),
-        })
+    fn part2(&self, map: &Self::Input, context: &Context) -> Result<Option<String>, Error> {
+        let bursts = context.param("part2_bursts")?;
+        Ok(Some(
+            part(&mut map.clone(), bursts, true, &context.cancel)?.to_string(),
+        ))
     }
 }
 
@@ -25,6 +40,7 @@ struct Coord {
     y: i64,
 }
 
+#[derive(Clone)]
 enum NodeState {
     Clean,
     Weakened,
@@ -51,13 +67,14 @@ impl NodeState {
     }
 }
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     nodes: HashMap<Coord, NodeState>,
     center: Coord,
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut nodes = HashMap::new();
@@ -180,25 +197,20 @@ impl Carrier {
     }
 }
 
-fn part1(map: &mut Map) -> usize {
-    part(map, 10_000, false)
-}
-
-fn part2(map: &mut Map) -> usize {
-    part(map, 10_000_000, true)
-}
-
-fn part(map: &mut Map, iterations: usize, evolved: bool) -> usize {
+fn part(map: &mut Map, iterations: usize, evolved: bool, cancel: &Cancel) -> Result<usize, Error> {
     let mut carrier = Carrier::new(map.center.clone(), evolved);
     let mut infections = 0;
 
-    for _ in 0..iterations {
+    for i in 0..iterations {
         if carrier.burst(map) {
             infections += 1;
         }
+        if i % CHECK_EVERY == 0 {
+            cancel.check()?;
+        }
     }
 
-    infections
+    Ok(infections)
 }
 
 #[cfg(test)]
@@ -206,13 +218,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn example() {
-        let input = "..#
-#..
-...";
-        let mut map = input.parse::<Map>().unwrap();
-        assert_eq!(part1(&mut map), 5587);
-        let mut map = input.parse::<Map>().unwrap();
-        assert_eq!(part(&mut map, 100, true), 26);
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
@@ -0,0 +1,446 @@
+use std::{fmt, str::FromStr};
+
+use crate::days::{
+    bytecode::{self, Bytecode, Event, Lower, Lowering, Processor, Test},
+    day::*,
+    vm::{
+        self, Debuggee, Factors, Flow, InstructionSet, Machine, Operand, Register, Registers, Value,
+    },
+};
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Instruction>;
+    const PARAMS: &'static [Param] = &[bytecode::PARAM];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1(&self, instructions: &Self::Input, context: &Context) -> Result<String, Error> {
+        if bytecode::enabled(context)? {
+            let code = bytecode::compile(instructions);
+            let processor = run_compiled(&code, 0, &context.cancel)?;
+            return Ok(processor.muls.to_string());
+        }
+
+        let mut coprocessor = Coprocessor::new(instructions.clone());
+        coprocessor.trace(context.trace.as_ref(), "part 1");
+        coprocessor.run_cancellable(&context.cancel)?;
+
+        Ok(coprocessor.state.0.to_string())
+    }
+
+    /// With `a` set to 1 the program counts the composite numbers in a range by trial
+    /// multiplication, which only finishes in time once [`optimize`] replaced its loops.
+    fn part2(
+        &self,
+        instructions: &Self::Input,
+        context: &Context,
+    ) -> Result<Option<String>, Error> {
+        let optimized = optimize(instructions);
+        if bytecode::enabled(context)? {
+            let code = bytecode::compile(&optimized);
+            let processor = run_compiled(&code, 1, &context.cancel)?;
+            return Ok(Some(processor.registers[register('h')].to_string()));
+        }
+
+        let mut coprocessor = Coprocessor::new(optimized);
+        coprocessor.registers[register('a')] = 1;
+        coprocessor.trace(context.trace.as_ref(), "part 2");
+        coprocessor.run_cancellable(&context.cancel)?;
+
+        Ok(Some(coprocessor.registers[register('h')].to_string()))
+    }
+
+    /// Part 2 runs the optimized program, showing the superinstructions in its listing.
+    fn debug(&self, instructions: &Self::Input, part: Part) -> Option<Box<dyn Debuggee>> {
+        let coprocessor = match part {
+            Part::One => Coprocessor::new(instructions.clone()),
+            Part::Two => {
+                let mut coprocessor = Coprocessor::new(optimize(instructions));
+                coprocessor.registers[register('a')] = 1;
+                coprocessor
+            }
+        };
+        Some(Box::new(coprocessor))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Set(Register, Operand),
+    Sub(Register, Operand),
+    Mul(Register, Operand),
+    Jnz(Operand, Operand),
+    /// Replaces the head of a loop trying every `counter` up to `number` as a factor
+    /// together with `factor`; see [`optimize`].
+    Divides(Factors),
+    /// Replaces the head of a loop around [`Instruction::Divides`] trying every
+    /// `factor` up to `number`, each time starting `counter` from the operand.
+    IsComposite(Factors, Operand),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let kind = parts.next().ok_or("empty string")?;
+        let a = parts.next();
+        let b = parts.next();
+        let register = vm::register::<Self>;
+        let operand = vm::operand::<Self>;
+        match kind {
+            "set" => Ok(Instruction::Set(register(s, a)?, operand(s, b)?)),
+            "mul" => Ok(Instruction::Mul(register(s, a)?, operand(s, b)?)),
+            "sub" => Ok(Instruction::Sub(register(s, a)?, operand(s, b)?)),
+            "jnz" => Ok(Instruction::Jnz(operand(s, a)?, operand(s, b)?)),
+            _ => Err(ParseError::new(format!("unknown instruction: {}", kind)).spanning(s, kind)),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Set(register, value) => write!(f, "set {} {}", register, value),
+            Instruction::Sub(register, value) => write!(f, "sub {} {}", register, value),
+            Instruction::Mul(register, value) => write!(f, "mul {} {}", register, value),
+            Instruction::Jnz(value, offset) => write!(f, "jnz {} {}", value, offset),
+            Instruction::Divides(l) => write!(
+                f,
+                "divides {} {} {} {} {}",
+                l.flag, l.factor, l.counter, l.number, l.scratch
+            ),
+            Instruction::IsComposite(l, start) => write!(
+                f,
+                "is_composite {} {} {} {} {} {}",
+                l.flag, l.factor, l.counter, start, l.number, l.scratch
+            ),
+        }
+    }
+}
+
+/// Counts the `mul` instructions executed.
+#[derive(Debug, Default)]
+pub struct MulCount(pub usize);
+
+impl fmt::Display for MulCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mul count {}", self.0)
+    }
+}
+
+impl InstructionSet for Instruction {
+    const REGISTERS: usize = 8;
+    type State = MulCount;
+
+    fn execute(&self, registers: &mut Registers, mul_count: &mut MulCount) -> Flow {
+        match *self {
+            Instruction::Set(register, value) => registers[register] = registers.resolve(value),
+            Instruction::Sub(register, value) => registers[register] -= registers.resolve(value),
+            Instruction::Mul(register, value) => {
+                registers[register] *= registers.resolve(value);
+                mul_count.0 += 1;
+            }
+            Instruction::Jnz(value, offset) => {
+                if registers.resolve(value) != 0 {
+                    return Flow::Jump(registers.resolve(offset));
+                }
+            }
+            // Loops that never end run as written, starting with the replaced instruction.
+            Instruction::Divides(l) => match l.divides(registers) {
+                Some(muls) => {
+                    mul_count.0 += muls as usize;
+                    return Flow::Jump(DIVIDES_LEN as Value);
+                }
+                None => registers[l.scratch] = registers[l.factor],
+            },
+            Instruction::IsComposite(l, start) => match l.is_composite(registers, start) {
+                Some(muls) => {
+                    mul_count.0 += muls as usize;
+                    return Flow::Jump(IS_COMPOSITE_LEN as Value);
+                }
+                None => registers[l.counter] = registers.resolve(start),
+            },
+        }
+        Flow::Next
+    }
+}
+
+fn register(name: char) -> Register {
+    Register::named(name).expect("a register name")
+}
+
+const DIVIDES_LEN: usize = 9;
+const IS_COMPOSITE_LEN: usize = DIVIDES_LEN + 5;
+
+/// Recognises the trial multiplication loops in `instructions` whatever registers they
+/// use and puts a superinstruction computing their outcome at each loop head, in
+/// place of the first instruction of the loop:
+///
+/// ```text
+/// set e 2       <- is_composite f d e 2 b g
+/// set g d       <- divides f d e b g
+/// mul g e
+/// sub g b
+/// jnz g 2
+/// set f 0
+/// sub e -1
+/// set g e
+/// sub g b
+/// jnz g -8
+/// sub d -1
+/// set g d
+/// sub g b
+/// jnz g -13
+/// ```
+///
+/// The rest of each loop stays in place, so jumps keep their targets and the
+/// superinstructions run the replaced instruction instead when the loop would never
+/// end.
+pub fn optimize(instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut optimized = instructions.to_vec();
+    for pc in 0..instructions.len() {
+        let window = &instructions[pc..];
+        if let Some((factors, start)) = is_composite_loop(window) {
+            optimized[pc] = Instruction::IsComposite(factors, start);
+        } else if let Some(factors) = divides_loop(window) {
+            optimized[pc] = Instruction::Divides(factors);
+        }
+    }
+    optimized
+}
+
+/// Matches the inner loop at the start of `window`, counting `counter` up to `number`.
+fn divides_loop(window: &[Instruction]) -> Option<Factors> {
+    use Instruction::*;
+    use Operand::{Register as R, Value as V};
+
+    let [
+        Set(scratch, R(factor)),
+        Mul(s1, R(counter)),
+        Sub(s2, R(number)),
+        Jnz(R(s3), V(2)),
+        Set(flag, V(0)),
+        Sub(c1, V(-1)),
+        Set(s4, R(c2)),
+        Sub(s5, R(n1)),
+        Jnz(R(s6), V(-8)),
+        ..,
+    ] = *window
+    else {
+        return None;
+    };
+    let factors = Factors {
+        flag,
+        factor,
+        counter,
+        number,
+        scratch,
+    };
+    let scratches = [s1, s2, s3, s4, s5, s6];
+    let same =
+        scratches.iter().all(|&s| s == scratch) && c1 == counter && c2 == counter && n1 == number;
+    let registers = [flag, factor, counter, number, scratch];
+    let distinct = registers
+        .iter()
+        .enumerate()
+        .all(|(i, r)| !registers[..i].contains(r));
+    (same && distinct).then_some(factors)
+}
+
+/// Matches the outer loop at the start of `window`, counting `factor` up to `number`.
+fn is_composite_loop(window: &[Instruction]) -> Option<(Factors, Operand)> {
+    use Instruction::*;
+    use Operand::{Register as R, Value as V};
+
+    let [Set(counter, start), ..] = *window else {
+        return None;
+    };
+    let factors = divides_loop(window.get(1..)?)?;
+    let [Sub(f1, V(-1)), Set(s1, R(f2)), Sub(s2, R(n1)), Jnz(R(s3), V(-13)), ..] =
+        *window.get(1 + DIVIDES_LEN..)?
+    else {
+        return None;
+    };
+    let same = [f1, f2] == [factors.factor; 2]
+        && [s1, s2, s3] == [factors.scratch; 3]
+        && n1 == factors.number
+        && counter == factors.counter;
+    // The counter may not start from the registers the loops change or read.
+    let fixed = match start {
+        R(r) => ![
+            factors.flag,
+            factors.factor,
+            factors.counter,
+            factors.scratch,
+        ]
+        .contains(&r),
+        V(_) => true,
+    };
+    (same && fixed).then_some((factors, start))
+}
+
+pub type Coprocessor = Machine<Instruction>;
+
+/// Superinstructions lower to native operations continuing after their loops.
+impl Lower for Instruction {
+    fn lower(&self, at: &Lowering) -> bytecode::Op {
+        match *self {
+            Instruction::Set(register, value) => at.set(register, value),
+            Instruction::Sub(register, value) => at.sub(register, value),
+            Instruction::Mul(register, value) => at.mul(register, value),
+            Instruction::Jnz(value, offset) => at.jump(Test::NonZero, value, offset),
+            Instruction::Divides(l) => bytecode::Op::Divides(l, at.target(DIVIDES_LEN as Value)),
+            Instruction::IsComposite(l, start) => {
+                bytecode::Op::IsComposite(l, start, at.target(IS_COMPOSITE_LEN as Value))
+            }
+        }
+    }
+}
+
+/// Runs the compiled program with register `a` set to `a` until it halts.
+fn run_compiled<'a>(code: &'a Bytecode, a: Value, cancel: &Cancel) -> Result<Processor<'a>, Error> {
+    let mut processor = Processor::new(code);
+    processor.registers[register('a')] = a;
+    loop {
+        match processor.run(bytecode::SLICE) {
+            Event::Halted => return Ok(processor),
+            _ => cancel.check()?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::vm::Status;
+
+    /// The shape every puzzle input has; `a` decides between one number and a range.
+    const PROGRAM: &str = "set b 57
+set c b
+jnz a 2
+jnz 1 5
+mul b 100
+sub b -100000
+set c b
+sub c -17000
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23";
+
+    fn parse(program: &str) -> Vec<Instruction> {
+        let lines: Vec<_> = program.lines().map(str::to_owned).collect();
+        parse_lines(&lines).unwrap()
+    }
+
+    fn run(instructions: Vec<Instruction>) -> Coprocessor {
+        let mut coprocessor = Coprocessor::new(instructions);
+        assert_eq!(coprocessor.run(), Status::Halted);
+        coprocessor
+    }
+
+    #[test]
+    fn superinstructions() {
+        let instructions = parse(PROGRAM);
+        let optimized = optimize(&instructions);
+
+        assert_eq!(optimized[10].to_string(), "is_composite f d e 2 b g");
+        assert_eq!(optimized[11].to_string(), "divides f d e b g");
+        let changed: Vec<_> = (0..instructions.len())
+            .filter(|&pc| optimized[pc] != instructions[pc])
+            .collect();
+        assert_eq!(changed, [10, 11]);
+
+        let original = run(instructions.clone());
+        let optimized = run(optimized);
+        assert_eq!(optimized.registers, original.registers);
+        assert_eq!(optimized.state.0, original.state.0);
+        assert_eq!(original.state.0, 55 * 55);
+        for program in [&instructions, optimized.program()] {
+            let code = bytecode::compile(program);
+            let processor = run_compiled(&code, 0, &Cancel::default()).unwrap();
+            assert_eq!(processor.registers, original.registers);
+            assert_eq!(processor.muls, 55 * 55);
+        }
+    }
+
+    #[test]
+    fn cancelled() {
+        let instructions = parse("jnz 1 0");
+        for bytecode in [0, 1] {
+            let mut context = Context::default();
+            context.params.set("bytecode", bytecode);
+            context.cancel.cancel();
+            assert!(matches!(
+                Instance.part1(&instructions, &context),
+                Err(Error::Cancelled)
+            ));
+        }
+    }
+
+    #[test]
+    fn inner_loop_only() {
+        // Trying even factors only no longer fits `is_composite`.
+        let program = PROGRAM
+            .replacen("set b 57", "set b 58", 1)
+            .replacen("sub d -1", "sub d -2", 1);
+        let instructions = parse(&program);
+        let optimized = optimize(&instructions);
+
+        assert!(matches!(optimized[10], Instruction::Set(..)));
+        assert!(matches!(optimized[11], Instruction::Divides(..)));
+
+        let code = bytecode::compile(&optimized);
+        let compiled = run_compiled(&code, 0, &Cancel::default()).unwrap();
+        let original = run(instructions);
+        let optimized = run(optimized);
+        assert_eq!(optimized.registers, original.registers);
+        assert_eq!(optimized.state.0, original.state.0);
+        assert_eq!(compiled.registers, original.registers);
+        assert_eq!(compiled.muls as usize, original.state.0);
+        assert_eq!(optimized.registers[register('h')], 1);
+    }
+
+    #[test]
+    fn composite_range() {
+        let instructions = parse(&PROGRAM.replacen("set b 57", "set b 1", 1));
+        // Composites among 100100, 100117, ..., 117100.
+        let expected = (0..=1000)
+            .map(|i| 100100 + 17 * i)
+            .filter(|&n| (2..n).take_while(|d| d * d <= n).any(|d| n % d == 0))
+            .count();
+        for bytecode in [0, 1] {
+            let mut context = Context::default();
+            context.params.set("bytecode", bytecode);
+            assert_eq!(
+                Instance.part2(&instructions, &context).unwrap(),
+                Some(expected.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<Component>;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "0/2\n2/2\n2/3\n3/4\n3/5\n0/1\n10/1\n9/10",
+        "31",
+        "19",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1(&self, components: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(strongest_bridge(components).to_string())
+    }
+
+    fn part2(&self, components: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(longest_bridge(components).to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Component {
+    port_a: u8,
+    port_b: u8,
+}
+
+impl FromStr for Component {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (a, b) = s
+            .split_once('/')
+            .ok_or_else(|| ParseError::new("missing port b").at_end(s))?;
+        let port_a = a
+            .parse()
+            .map_err(|e| ParseError::new(format!("invalid port a: {}", e)).spanning(s, a))?;
+        let port_b = b
+            .parse()
+            .map_err(|e| ParseError::new(format!("invalid port b: {}", e)).spanning(s, b))?;
+
+        Ok(Component { port_a, port_b })
+    }
+}
+
+fn strongest_bridge(components: &[Component]) -> u32 {
+    best_bridge(components, |_, strength| strength)
+}
+
+/// Strength of the longest bridge, the strongest of them if there are several.
+fn longest_bridge(components: &[Component]) -> u32 {
+    best_bridge(components, |length, strength| (length, strength))
+}
+
+/// Strength of the bridge that `rank` puts highest, given its length and strength.
+fn best_bridge<K: Ord>(components: &[Component], rank: impl Fn(usize, u32) -> K) -> u32 {
+    let mut component_map = HashMap::new();
+
+    for component in components {
+        component_map
+            .entry(component.port_a)
+            .or_insert(vec![])
+            .push(component);
+        component_map
+            .entry(component.port_b)
+            .or_insert(vec![])
+            .push(component);
+    }
+
+    struct State<'a> {
+        components: HashSet<&'a Component>,
+        port: u8,
+        strength: u32,
+    }
+
+    fn build_bridge<K: Ord>(
+        component_map: &HashMap<u8, Vec<&Component>>,
+        state: &State,
+        rank: &impl Fn(usize, u32) -> K,
+        best: &mut Option<(K, u32)>,
+    ) {
+        let mut found = false;
+
+        for component in component_map.get(&state.port).unwrap_or(&vec![]) {
+            if state.components.contains(component) {
+                continue;
+            }
+
+            let next_port = if component.port_a == state.port {
+                component.port_b
+            } else {
+                component.port_a
+            };
+
+            let mut next_state = State {
+                components: state.components.clone(),
+                port: next_port,
+                strength: state.strength + component.port_a as u32 + component.port_b as u32,
+            };
+
+            next_state.components.insert(component);
+
+            build_bridge(component_map, &next_state, rank, best);
+
+            found = true;
+        }
+
+        if !found {
+            let key = rank(state.components.len(), state.strength);
+            if best.as_ref().is_none_or(|(best, _)| key > *best) {
+                *best = Some((key, state.strength));
+            }
+        }
+    }
+
+    let mut best = None;
+    build_bridge(
+        &component_map,
+        &State {
+            components: HashSet::new(),
+            port: 0,
+            strength: 0,
+        },
+        &rank,
+        &mut best,
+    );
+    best.map_or(0, |(_, strength)| strength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,270 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = TuringMachine;
+    const EXAMPLES: &'static [Example] = &[Example::part_one(
+        "Begin in state A.
+Perform a diagnostic checksum after 6 steps.
+
+In state A:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state B.
+  If the current value is 1:
+    - Write the value 0.
+    - Move one slot to the left.
+    - Continue with state B.
+
+In state B:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the left.
+    - Continue with state A.
+  If the current value is 1:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state A.",
+        "3",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines.join("\n").parse()
+    }
+
+    fn part1(&self, machine: &Self::Input, context: &Context) -> Result<String, Error> {
+        Ok(machine.clone().checksum(&context.cancel)?.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum State {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+}
+
+#[derive(Clone)]
+struct Transition {
+    write: bool,
+    move_right: bool,
+    next_state: State,
+}
+
+#[derive(Clone)]
+pub struct TuringMachine {
+    tape_right: Vec<bool>,
+    tape_left: Vec<bool>,
+    cursor: i64,
+    state: State,
+    transitions: HashMap<(State, bool), Transition>,
+    checksum_after: usize,
+}
+
+impl FromStr for TuringMachine {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Lines::new(s);
+
+        let initial_state = lines.word(3, "initial state")?;
+        let initial_state = parse_state(&lines, initial_state)?;
+
+        let checksum_after = lines.word(5, "checksum after value")?;
+        let checksum_after = checksum_after.parse::<usize>().map_err(|e| {
+            lines.error(
+                format!("invalid checksum after value: {}", e),
+                checksum_after,
+            )
+        })?;
+
+        let mut transitions = HashMap::new();
+        while lines.skip() {
+            let state = lines.word(2, "state")?;
+            let state = parse_state(&lines, state)?;
+
+            let (t, v) = parse_transition(&mut lines)?;
+            transitions.insert((state, v), t);
+            let (t, v) = parse_transition(&mut lines)?;
+            transitions.insert((state, v), t);
+        }
+
+        Ok(TuringMachine {
+            tape_right: vec![false],
+            tape_left: vec![],
+            cursor: 0,
+            state: initial_state,
+            transitions,
+            checksum_after,
+        })
+    }
+}
+
+/// Walks the blueprint line by line so errors can point at the word that is wrong.
+struct Lines<'a> {
+    lines: std::str::Lines<'a>,
+    current: &'a str,
+    number: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(s: &'a str) -> Lines<'a> {
+        Lines {
+            lines: s.lines(),
+            current: "",
+            number: 0,
+        }
+    }
+
+    /// Moves past a line without reading it, returning false at the end of the input.
+    fn skip(&mut self) -> bool {
+        match self.lines.next() {
+            Some(line) => {
+                self.current = line;
+                self.number += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves to the next line and returns its `n`th word without trailing punctuation.
+    fn word(&mut self, n: usize, name: &str) -> Result<&'a str, ParseError> {
+        if !self.skip() {
+            return Err(ParseError::new(format!("missing {}", name)).on_line(self.number + 1));
+        }
+        let line = self.current;
+        line.split_whitespace()
+            .nth(n)
+            .map(|word| word.trim_end_matches([':', '.']))
+            .ok_or_else(|| {
+                ParseError::new(format!("missing {}", name))
+                    .on_line(self.number)
+                    .at_end(line)
+            })
+    }
+
+    fn error(&self, message: String, word: &str) -> ParseError {
+        ParseError::new(message)
+            .on_line(self.number)
+            .spanning(self.current, word)
+    }
+}
+
+fn parse_state(lines: &Lines, word: &str) -> Result<State, ParseError> {
+    match word {
+        "A" => Ok(State::A),
+        "B" => Ok(State::B),
+        "C" => Ok(State::C),
+        "D" => Ok(State::D),
+        "E" => Ok(State::E),
+        "F" => Ok(State::F),
+        _ => Err(lines.error(format!("invalid state value: {}", word), word)),
+    }
+}
+
+fn parse_bit(lines: &Lines, word: &str) -> Result<bool, ParseError> {
+    match word {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(lines.error(format!("invalid value {}", word), word)),
+    }
+}
+
+fn parse_transition(lines: &mut Lines) -> Result<(Transition, bool), ParseError> {
+    let value = lines.word(5, "current value")?;
+    let value = parse_bit(lines, value)?;
+
+    let write = lines.word(4, "write value")?;
+    let write = parse_bit(lines, write)?;
+
+    let move_right = match lines.word(6, "move value")? {
+        "right" => true,
+        "left" => false,
+        v => return Err(lines.error(format!("invalid move value {}", v), v)),
+    };
+
+    let next_state = lines.word(4, "next state")?;
+    let next_state = parse_state(lines, next_state)?;
+
+    Ok((
+        Transition {
+            write,
+            move_right,
+            next_state,
+        },
+        value,
+    ))
+}
+
+impl TuringMachine {
+    /// Runs the machine for its configured number of steps and counts the ones on the tape.
+    pub fn checksum(&mut self, cancel: &Cancel) -> Result<usize, Error> {
+        for i in 0..self.checksum_after {
+            if i % CHECK_EVERY == 0 {
+                cancel.check()?;
+            }
+            let current = self.get_current();
+            let &Transition {
+                write,
+                move_right,
+                next_state,
+            } = &self.transitions[&(self.state, current)];
+            self.state = next_state;
+            self.set_current(write);
+            if move_right {
+                self.cursor += 1;
+            } else {
+                self.cursor -= 1;
+            }
+        }
+
+        Ok(self.tape_left.iter().filter(|t| **t).count()
+            + self.tape_right.iter().filter(|t| **t).count())
+    }
+
+    fn get_current(&mut self) -> bool {
+        if self.cursor >= 0 {
+            let i = self.cursor as usize;
+            if i < self.tape_right.len() {
+                self.tape_right[i]
+            } else {
+                self.tape_right.push(false);
+                false
+            }
+        } else {
+            let i = (-1 - self.cursor) as usize;
+            if i < self.tape_left.len() {
+                self.tape_left[i]
+            } else {
+                self.tape_left.push(false);
+                false
+            }
+        }
+    }
+
+    fn set_current(&mut self, write: bool) {
+        if self.cursor >= 0 {
+            self.tape_right[self.cursor as usize] = write
+        } else {
+            self.tape_left[(-1 - self.cursor) as usize] = write
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -1,24 +1,34 @@
 use std::collections::HashMap;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let parsed = lines
+    type Input = u32;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("1", "0"),
+        Example::part_one("12", "3"),
+        Example::part_one("23", "2"),
+        Example::part_one("1024", "31"),
+        Example::part_two("1", "2"),
+        Example::part_two("804", "806"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines
             .first()
-            .unwrap_or(&"".to_string())
+            .map_or("", |line| line.as_str())
             .parse::<u32>()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| ParseError::new(e.to_string()).on_line(1))
+    }
 
-        let part1 = steps(parsed).to_string();
-        let part2 = allocate(parsed).to_string();
+    fn part1(&self, number: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(steps(*number).to_string())
+    }
 
-        Ok(DayResult {
-            part1,
-            part2: Some(part2),
-        })
+    fn part2(&self, number: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(allocate(*number).to_string()))
     }
 }
 
@@ -78,7 +88,7 @@ fn steps(number: u32) -> u32 {
     let pos = Spiral::new()
         .nth(number as usize - 1)
         .expect("infinite iterator");
-    pos.0.abs() as u32 + pos.1.abs() as u32
+    pos.0.unsigned_abs() + pos.1.unsigned_abs()
 }
 
 fn allocate(target: u32) -> u32 {
@@ -114,16 +124,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_gives_correct_steps() {
-        assert_eq!(steps(1), 0);
-        assert_eq!(steps(12), 3);
-        assert_eq!(steps(23), 2);
-        assert_eq!(steps(1024), 31);
-    }
-
-    #[test]
-    fn it_gives_correct_allocation() {
-        assert_eq!(allocate(1), 2);
-        assert_eq!(allocate(804), 806);
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
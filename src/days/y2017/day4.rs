@@ -0,0 +1,57 @@
+use itertools::Itertools;
+
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<String>;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("aa bb cc dd ee", "1"),
+        Example::part_one("aa bb cc dd aa", "0"),
+        Example::part_one("aa bb cc dd aaa", "1"),
+        Example::part_two("abcde fghij", "1"),
+        Example::part_two("abcde xyz ecdab", "0"),
+        Example::part_two("a ab abc abd abf abj", "1"),
+        Example::part_two("iiii oiii ooii oooi oooo", "1"),
+        Example::part_two("oiii ioii iioi iiio", "0"),
+    ];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(&self, lines: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(lines.iter().filter(|s| is_valid(s)).count().to_string())
+    }
+
+    fn part2(&self, lines: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        let valid = lines.iter().filter(|s| is_valid_anagram(s)).count();
+        Ok(Some(valid.to_string()))
+    }
+}
+
+fn is_valid(phrase: &str) -> bool {
+    let words: Vec<_> = phrase.split(" ").collect();
+    let unique = words.iter().unique();
+    words.len() == unique.count()
+}
+
+fn is_valid_anagram(phrase: &str) -> bool {
+    let words: Vec<_> = phrase.split(" ").collect();
+    let unique = words
+        .iter()
+        .map(|word| word.chars().sorted().collect::<String>())
+        .unique();
+    words.len() == unique.count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Instructions;
+    const EXAMPLES: &'static [Example] = &[Example::new("0\n3\n0\n1\n-3", "5", "10")];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Instructions::parse(lines)
+    }
+
+    fn part1(&self, instructions: &Self::Input, context: &Context) -> Result<String, Error> {
+        Ok(instructions
+            .clone()
+            .steps(false, &context.cancel)?
+            .to_string())
+    }
+
+    fn part2(
+        &self,
+        instructions: &Self::Input,
+        context: &Context,
+    ) -> Result<Option<String>, Error> {
+        Ok(Some(
+            instructions
+                .clone()
+                .steps(true, &context.cancel)?
+                .to_string(),
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub struct Instructions(Vec<i32>);
+
+impl Instructions {
+    fn parse(lines: &[String]) -> Result<Self, ParseError> {
+        let i = lines
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                s.parse::<i32>().map_err(|e| {
+                    ParseError::new(format!("failed to parse ({}): {}", s, e))
+                        .on_line(i + 1)
+                        .spanning(s, s)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Instructions(i))
+    }
+
+    fn steps(&mut self, strange: bool, cancel: &Cancel) -> Result<usize, Error> {
+        let mut pointer = 0;
+        let mut steps = 0;
+
+        loop {
+            if pointer >= self.0.len() {
+                return Ok(steps);
+            }
+            if steps % CHECK_EVERY == 0 {
+                cancel.check()?;
+            }
+            steps += 1;
+            let next = (pointer as i32) + self.0[pointer];
+            if strange && self.0[pointer] >= 3 {
+                self.0[pointer] -= 1;
+            } else {
+                self.0[pointer] += 1;
+            }
+            if next < 0 {
+                return Ok(steps);
+            }
+            pointer = next as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
@@ -1,30 +1,40 @@
 use std::collections::HashMap;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let line = lines.first().ok_or("expected line".to_owned())?;
-        let (p1, p2) = Memory::parse(line)?.redistribute_cycle();
+    type Input = Memory;
+    const EXAMPLES: &'static [Example] = &[Example::new("0 2 7 0", "5", "4")];
 
-        Ok(DayResult {
-            part1: p1.to_string(),
-            part2: Some(p2.to_string()),
-        })
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        let line = lines.first().ok_or("expected line")?;
+        Memory::parse(line).map_err(|e| e.on_line(1))
+    }
+
+    fn part1(&self, memory: &Self::Input, _context: &Context) -> Result<String, Error> {
+        let (cycles, _) = memory.clone().redistribute_cycle();
+        Ok(cycles.to_string())
+    }
+
+    fn part2(&self, memory: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        let (_, loop_size) = memory.clone().redistribute_cycle();
+        Ok(Some(loop_size.to_string()))
     }
 }
 
-struct Memory(Vec<u8>);
+#[derive(Clone)]
+pub struct Memory(Vec<u8>);
 
 impl Memory {
-    fn parse(line: &str) -> Result<Self, String> {
+    fn parse(line: &str) -> Result<Self, ParseError> {
         let i = line
             .split_whitespace()
             .map(|s| {
-                s.parse::<u8>()
-                    .map_err(|e| format!("failed to parse ({}): {}", s, e))
+                s.parse::<u8>().map_err(|e| {
+                    ParseError::new(format!("failed to parse ({}): {}", s, e)).spanning(line, s)
+                })
             })
             .collect::<Result<_, _>>()?;
         Ok(Memory(i))
@@ -74,7 +84,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn example() {
-        assert_eq!(Memory(vec![0, 2, 7, 0]).redistribute_cycle(), (5, 4))
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
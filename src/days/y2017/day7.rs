@@ -1,23 +1,41 @@
-use std::{
-    collections::{HashMap, HashSet},
-    usize,
-};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let tower = Tower::parse(&lines)?;
-        let part1 = tower.root.clone();
-
-        Ok(DayResult {
-            part1,
-            part2: Some(tower.balance().to_string()),
-        })
+    type Input = Tower;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "pbga (66)
+xhth (57)
+ebii (61)
+havc (66)
+ktlj (57)
+fwft (72) -> ktlj, cntj, xhth
+qoyq (66)
+padx (45) -> pbga, havc, qoyq
+tknk (41) -> ugml, padx, fwft
+jptl (61)
+ugml (68) -> gyxo, ebii, jptl
+gyxo (61)
+cntj (57)",
+        "tknk",
+        "60",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Tower::parse(lines)
+    }
+
+    fn part1(&self, tower: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(tower.root.clone())
+    }
+
+    fn part2(&self, tower: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(tower.balance().to_string()))
     }
 }
 
@@ -26,16 +44,16 @@ struct Program {
     weight: usize,
 }
 
-struct Tower {
+pub struct Tower {
     programs: HashMap<String, Program>,
     root: String,
 }
 
 impl Tower {
-    fn parse(s: &Vec<String>) -> Result<Self, String> {
+    fn parse(s: &[String]) -> Result<Self, ParseError> {
         let mut programs = HashMap::new();
 
-        for l in s {
+        for (i, l) in s.iter().enumerate() {
             let mut children = Vec::new();
             if l.contains(" -> ") {
                 let s: Vec<_> = l.split(" -> ").collect();
@@ -44,7 +62,9 @@ impl Tower {
 
             let s: Vec<_> = l.split(" ").collect();
             if s.len() < 2 {
-                return Err(format!("bad format {}", l));
+                return Err(ParseError::new(format!("bad format {}", l))
+                    .on_line(i + 1)
+                    .spanning(l, l));
             }
             let name = s[0];
             let weight_raw = s[1];
@@ -53,7 +73,11 @@ impl Tower {
             let weight = weight_raw
                 .trim_matches(brackets)
                 .parse::<usize>()
-                .map_err(|e| format!("failed to parse weight {}: {}", weight_raw, e))?;
+                .map_err(|e| {
+                    ParseError::new(format!("failed to parse weight {}: {}", weight_raw, e))
+                        .on_line(i + 1)
+                        .spanning(l, weight_raw)
+                })?;
             programs.insert(name.to_owned(), Program { children, weight });
         }
 
@@ -64,7 +88,7 @@ impl Tower {
         let root = programs
             .keys()
             .find(|p| !children.contains(p))
-            .ok_or("could not find root".to_owned())?
+            .ok_or("could not find root")?
             .to_owned();
 
         Ok(Tower { programs, root })
@@ -116,25 +140,7 @@ impl Tower {
 mod tests {
     use super::*;
     #[test]
-    fn it_parses() {
-        let lines = vec![
-            "pbga (66)".to_owned(),
-            "xhth (57)".to_owned(),
-            "ebii (61)".to_owned(),
-            "havc (66)".to_owned(),
-            "ktlj (57)".to_owned(),
-            "fwft (72) -> ktlj, cntj, xhth".to_owned(),
-            "qoyq (66)".to_owned(),
-            "padx (45) -> pbga, havc, qoyq".to_owned(),
-            "tknk (41) -> ugml, padx, fwft".to_owned(),
-            "jptl (61)".to_owned(),
-            "ugml (68) -> gyxo, ebii, jptl".to_owned(),
-            "gyxo (61)".to_owned(),
-            "cntj (57)".to_owned(),
-        ];
-
-        let t = Tower::parse(&lines).unwrap();
-        assert_eq!(t.root, "tknk");
-        assert_eq!(t.balance(), 60);
+    fn examples() {
+        assert_examples(&Instance);
     }
 }
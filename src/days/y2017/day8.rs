@@ -2,26 +2,39 @@ use std::{collections::HashMap, str::FromStr};
 
 use itertools::Itertools;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, input: Vec<String>) -> Result<DayResult, String> {
-        let parsed = input
-            .iter()
-            .map(|i| i.parse::<Instruction>())
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap();
+    type Input = Vec<Instruction>;
+    const EXAMPLES: &'static [Example] = &[Example::new(
+        "b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10",
+        "1",
+        "10",
+    )];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
 
+    fn part1(&self, instructions: &Self::Input, _context: &Context) -> Result<String, Error> {
         let mut computer = Computer::new();
-        computer.run(&parsed);
+        computer.run(instructions);
+        Ok(computer.max_reg().to_string())
+    }
 
-        let part1 = computer.max_reg().to_string();
-        Ok(DayResult {
-            part1,
-            part2: Some(computer.max_ever.to_string()),
-        })
+    fn part2(
+        &self,
+        instructions: &Self::Input,
+        _context: &Context,
+    ) -> Result<Option<String>, Error> {
+        let mut computer = Computer::new();
+        computer.run(instructions);
+        Ok(Some(computer.max_ever.to_string()))
     }
 }
 
@@ -41,7 +54,7 @@ enum Cond {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-struct Instruction {
+pub struct Instruction {
     reg: String,
     inc: bool,
     amount: i32,
@@ -51,19 +64,30 @@ struct Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts = s.split(" ").collect_vec();
         if parts.len() != 7 {
-            return Err("".to_owned());
+            return Err(
+                ParseError::new(format!("expected 7 words, found {}", parts.len())).spanning(s, s),
+            );
         }
 
         let reg = parts[0].to_owned();
-        let inc = parts[1] == "inc";
-        let amount = parts[2]
-            .parse()
-            .map_err(|e| format!("failed to parse amount {}: {}", parts[2], e))?;
+        let inc = match parts[1] {
+            "inc" => true,
+            "dec" => false,
+            unknown => {
+                return Err(
+                    ParseError::new(format!("Unknown operation {}", unknown)).spanning(s, unknown)
+                )
+            }
+        };
+        let amount = parts[2].parse().map_err(|e| {
+            ParseError::new(format!("failed to parse amount {}: {}", parts[2], e))
+                .spanning(s, parts[2])
+        })?;
 
         let cond_reg = parts[4].to_owned();
         let cond_type = match parts[5] {
@@ -73,11 +97,16 @@ impl FromStr for Instruction {
             "<=" => Cond::Lte,
             "==" => Cond::Eq,
             "!=" => Cond::Neq,
-            unknown => return Err(format!("Unknown condition {}", unknown)),
+            unknown => {
+                return Err(
+                    ParseError::new(format!("Unknown condition {}", unknown)).spanning(s, unknown)
+                )
+            }
         };
-        let cond_amount = parts[6]
-            .parse()
-            .map_err(|e| format!("failed to parse amount {}: {}", parts[2], e))?;
+        let cond_amount = parts[6].parse().map_err(|e| {
+            ParseError::new(format!("failed to parse amount {}: {}", parts[6], e))
+                .spanning(s, parts[6])
+        })?;
 
         Ok(Instruction {
             reg,
@@ -157,19 +186,10 @@ mod tests {
     }
 
     #[test]
-    fn example() {
-        let input = vec![
-            "b inc 5 if a > 1",
-            "a inc 1 if b < 5",
-            "c dec -10 if a >= 1",
-            "c inc -20 if c == 10",
-        ];
-        let parsed = input
-            .iter()
-            .map(|i| i.parse::<Instruction>())
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap();
+    fn examples() {
+        assert_examples(&Instance);
 
+        let parsed = Instance.parse(&Instance::EXAMPLES[0].lines()).unwrap();
         let mut computer = Computer::new();
         computer.run(&parsed);
         assert_eq!(computer.get_reg("a"), 1);
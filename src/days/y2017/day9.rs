@@ -1,42 +1,72 @@
 use std::str::FromStr;
 
-use super::day::*;
+use crate::days::day::*;
 
 pub struct Instance;
 
 impl Day for Instance {
-    fn run(&self, lines: Vec<String>) -> Result<DayResult, String> {
-        let thing: Thing = lines.first().ok_or("empty lines".to_string())?.parse()?;
+    type Input = Thing;
+    const EXAMPLES: &'static [Example] = &[
+        Example::part_one("{}", "1"),
+        Example::part_one("{{{}}}", "6"),
+        Example::part_one("{{},{}}", "5"),
+        Example::part_one("{{{},{},{{}}}}", "16"),
+        Example::part_one("{<a>,<a>,<a>,<a>}", "1"),
+        Example::part_one("{{<ab>},{<ab>},{<ab>},{<ab>}}", "9"),
+        Example::part_one("{{<!!>},{<!!>},{<!!>},{<!!>}}", "9"),
+        Example::part_one("{{<a!>},{<a!>},{<a!>},{<ab>}}", "3"),
+        Example::part_two("<>", "0"),
+        Example::part_two("<random characters>", "17"),
+        Example::part_two("<<<<>", "3"),
+        Example::part_two("<{!>}>", "2"),
+        Example::part_two("<!!>", "0"),
+        Example::part_two("<!!!>>", "0"),
+        Example::part_two("<{o\"i!a,<{i<a>", "10"),
+    ];
 
-        let part1 = thing.total_score().to_string();
-        Ok(DayResult {
-            part1,
-            part2: Some(thing.total_garbage().to_string()),
-        })
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        lines
+            .first()
+            .ok_or("empty lines")?
+            .parse()
+            .map_err(|e: ParseError| e.on_line(1))
+    }
+
+    fn part1(&self, thing: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Ok(thing.total_score().to_string())
+    }
+
+    fn part2(&self, thing: &Self::Input, _context: &Context) -> Result<Option<String>, Error> {
+        Ok(Some(thing.total_garbage().to_string()))
     }
 }
 
-enum Thing {
+pub enum Thing {
     Group { children: Vec<Thing>, score: usize },
     Garbage { content: String },
 }
 
 impl FromStr for Thing {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse(s: &str, i: usize, score: usize) -> Result<(Thing, usize), String> {
-            match &s[i..i + 1] {
+        fn char_at(s: &str, i: usize) -> Result<&str, ParseError> {
+            s.get(i..i + 1)
+                .ok_or_else(|| ParseError::new("unexpected end of stream").at_end(s))
+        }
+
+        fn parse(s: &str, i: usize, score: usize) -> Result<(Thing, usize), ParseError> {
+            match char_at(s, i)? {
                 "{" => {
                     let mut j = i + 1;
                     let mut children = Vec::new();
                     loop {
-                        if &s[j..j + 1] == "}" {
+                        if char_at(s, j)? == "}" {
                             return Ok((Thing::Group { children, score }, j + 1));
                         } else {
                             let (child, o) = parse(s, j, score + 1)?;
                             children.push(child);
-                            if &s[o..o + 1] == "," {
+                            if char_at(s, o)? == "," {
                                 j = o + 1;
                             } else {
                                 j = o;
@@ -52,7 +82,7 @@ impl FromStr for Thing {
                         if escape {
                             escape = false;
                         } else {
-                            match &s[j..j + 1] {
+                            match char_at(s, j)? {
                                 "!" => escape = true,
                                 ">" => {
                                     return Ok((
@@ -68,7 +98,7 @@ impl FromStr for Thing {
                         j += 1;
                     }
                 }
-                unknown => return Err(format!("unhandled char {}", unknown)),
+                unknown => Err(ParseError::new(format!("unhandled char {}", unknown)).at(i..i + 1)),
             }
         }
 
@@ -102,34 +132,6 @@ mod tests {
 
     #[test]
     fn examples() {
-        let examples = vec![
-            ("{}", 1),
-            ("{{{}}}", 6),
-            ("{{},{}}", 5),
-            ("{{{},{},{{}}}}", 16),
-            ("{<a>,<a>,<a>,<a>}", 1),
-            ("{{<ab>},{<ab>},{<ab>},{<ab>}}", 9),
-            ("{{<!!>},{<!!>},{<!!>},{<!!>}}", 9),
-            ("{{<a!>},{<a!>},{<a!>},{<ab>}}", 3),
-        ];
-        for (input, output) in examples {
-            let i: Thing = input.parse().unwrap();
-            assert_eq!(i.total_score(), output, "input {}", input)
-        }
-
-        let garbage = vec![
-            ("<>", 0),
-            ("<random characters>", 17),
-            ("<<<<>", 3),
-            ("<{!>}>", 2),
-            ("<!!>", 0),
-            ("<!!!>>", 0),
-            ("<{o\"i!a,<{i<a>", 10),
-        ];
-
-        for (input, output) in garbage {
-            let i: Thing = input.parse().unwrap();
-            assert_eq!(i.total_garbage(), output, "input {}", input)
-        }
+        assert_examples(&Instance);
     }
 }
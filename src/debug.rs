@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead, Write},
+};
+
+use aoc17::days::vm::{Debuggee, Status};
+
+const HELP: &str = "\
+step [N]       execute N instructions, 1 by default
+continue       run until a breakpoint, or the program blocks or halts
+break PC       stop before the instruction at PC
+break OP       stop before any instruction starting with OP, e.g. `rcv` or `jnz a`
+delete         remove all breakpoints
+registers [R]  show register R, or every register
+state          show the state besides the registers, e.g. message queues
+list           show the program with the current and breakpoint instructions marked
+quit           stop debugging";
+
+/// Where `continue` stops.
+#[derive(Default)]
+struct Breakpoints {
+    pcs: BTreeSet<usize>,
+    instructions: Vec<String>,
+}
+
+impl Breakpoints {
+    fn matches(&self, pc: usize, instruction: &str) -> bool {
+        self.pcs.contains(&pc)
+            || self
+                .instructions
+                .iter()
+                .any(|prefix| instruction.starts_with(prefix.as_str()))
+    }
+}
+
+/// Reads commands from `input` until it ends or asks to quit, driving `program`.
+pub fn debug(
+    program: &mut dyn Debuggee,
+    input: impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut breakpoints = Breakpoints::default();
+    writeln!(out, "Type `help` for the commands.")?;
+    show_next(program, out)?;
+    write!(out, "> ")?;
+    out.flush()?;
+
+    for line in input.lines() {
+        if !command(program, &mut breakpoints, line?.trim(), out)? {
+            break;
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Runs one command, returning false to stop debugging.
+fn command(
+    program: &mut dyn Debuggee,
+    breakpoints: &mut Breakpoints,
+    line: &str,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    let (name, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+    match name {
+        "" => {}
+        "s" | "step" => {
+            let count = match argument {
+                "" => Ok(1),
+                _ => argument.parse::<usize>(),
+            };
+            let Ok(count) = count else {
+                writeln!(out, "Invalid number of steps: {}", argument)?;
+                return Ok(true);
+            };
+            for _ in 0..count {
+                let status = program.step().status;
+                if status != Status::Running {
+                    return stopped(program, status, out).map(|_| true);
+                }
+            }
+            show_next(program, out)?;
+        }
+        "c" | "continue" => loop {
+            let status = program.step().status;
+            if status != Status::Running {
+                return stopped(program, status, out).map(|_| true);
+            }
+            let listing = program.listing();
+            let pc = program.pc();
+            let instruction = listing.get(pc);
+            if instruction.is_some_and(|instruction| breakpoints.matches(pc, instruction)) {
+                writeln!(out, "Breakpoint")?;
+                show_next(program, out)?;
+                break;
+            }
+        },
+        "b" | "break" if !argument.is_empty() => match argument.parse() {
+            Ok(pc) => {
+                breakpoints.pcs.insert(pc);
+            }
+            Err(_) => breakpoints.instructions.push(argument.to_owned()),
+        },
+        "d" | "delete" => *breakpoints = Breakpoints::default(),
+        "r" | "registers" => {
+            let count = program.register_count();
+            let registers = program.registers().iter().take(count);
+            for (register, value) in registers {
+                if argument.is_empty() || argument == register.to_string() {
+                    writeln!(out, "{} = {}", register, value)?;
+                }
+            }
+        }
+        "state" => writeln!(out, "{}", program.state())?,
+        "l" | "list" => {
+            for (pc, instruction) in program.listing().iter().enumerate() {
+                let current = if pc == program.pc() { '>' } else { ' ' };
+                let breakpoint = if breakpoints.matches(pc, instruction) {
+                    '*'
+                } else {
+                    ' '
+                };
+                writeln!(out, "{}{} {:>3}  {}", current, breakpoint, pc, instruction)?;
+            }
+        }
+        "h" | "help" => writeln!(out, "{}", HELP)?,
+        "q" | "quit" => return Ok(false),
+        _ => writeln!(
+            out,
+            "Unknown command: {}; type `help` for the commands",
+            line
+        )?,
+    }
+    Ok(true)
+}
+
+fn show_next(program: &dyn Debuggee, out: &mut impl Write) -> io::Result<()> {
+    let listing = program.listing();
+    let pc = program.pc();
+    let current = program
+        .current()
+        .map_or(String::new(), |current| format!("{}, ", current));
+    match listing.get(pc) {
+        Some(instruction) => writeln!(out, "{}pc {}: {}", current, pc, instruction),
+        None => writeln!(out, "{}pc {}: outside the program", current, pc),
+    }
+}
+
+fn stopped(program: &dyn Debuggee, status: Status, out: &mut impl Write) -> io::Result<()> {
+    match status {
+        Status::Blocked => writeln!(out, "Blocked")?,
+        Status::Halted => writeln!(out, "Halted")?,
+        Status::Running => {}
+    }
+    show_next(program, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc17::{days, Part};
+
+    use super::*;
+
+    #[test]
+    fn session() {
+        let lines: Vec<_> = ["set a 3", "sub a 1", "jnz a -1", "mul a a"]
+            .map(str::to_owned)
+            .into();
+        let mut program = days::get(2017, 23)
+            .unwrap()
+            .program
+            .debug(&lines, Part::One)
+            .unwrap()
+            .unwrap();
+
+        let commands = "step\nbreak jnz\ncontinue\nregisters a\ndelete\nbreak 3\nlist\ncontinue\nstep 5\nstate\n";
+        let mut out = Vec::new();
+        debug(program.as_mut(), commands.as_bytes(), &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "Type `help` for the commands.
+pc 0: set a 3
+> pc 1: sub a 1
+> > Breakpoint
+pc 2: jnz a -1
+> a = 2
+> > >      0  set a 3
+     1  sub a 1
+>    2  jnz a -1
+ *   3  mul a a
+> Breakpoint
+pc 3: mul a a
+> Halted
+pc 4: outside the program
+> mul count 1
+> "
+        );
+    }
+}
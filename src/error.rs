@@ -0,0 +1,239 @@
+use std::{fmt, io, ops::Range, path::PathBuf, time::Duration};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The command line asked for something that doesn't exist, e.g. an unknown day.
+    Usage(String),
+    Io(io::Error),
+    MissingInput(PathBuf),
+    Parse(ParseError),
+    Solve(String),
+    /// A day panicked; holds the panic message and where it happened.
+    Panic(String),
+    /// A day noticed its [`Cancel`](crate::days::day::Cancel) token had been cancelled.
+    Cancelled,
+    TimedOut(Duration),
+    /// The input server couldn't be reached or didn't hand over an input.
+    Fetch(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// 1-based line of the input the error was found on, if known.
+    pub line: Option<usize>,
+    /// Byte range of the offending text within that line, if known.
+    pub span: Option<Range<usize>>,
+    /// The input the error was found in, attached by the runner for [`ParseError::snippet`].
+    pub source: Option<Source>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub name: String,
+    pub text: String,
+}
+
+impl Error {
+    pub fn solve(message: impl Into<String>) -> Error {
+        Error::Solve(message.into())
+    }
+
+    /// Attaches the input text to parse errors so they can render a [`ParseError::snippet`].
+    pub fn with_source(self, name: &str, text: &str) -> Error {
+        match self {
+            Error::Parse(error) => Error::Parse(error.with_source(name, text)),
+            error => error,
+        }
+    }
+
+    pub fn snippet(&self) -> Option<String> {
+        match self {
+            Error::Parse(error) => error.snippet(),
+            _ => None,
+        }
+    }
+
+    /// Process exit code for this kind of failure, following sysexits.h where it applies.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::Fetch(_) => 69,
+            Error::Parse(_) => 65,
+            Error::MissingInput(_) => 66,
+            Error::Solve(_) | Error::Panic(_) => 70,
+            Error::Io(_) => 74,
+            Error::Cancelled | Error::TimedOut(_) => 75,
+        }
+    }
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            line: None,
+            span: None,
+            source: None,
+        }
+    }
+
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at(mut self, span: Range<usize>) -> ParseError {
+        self.span = Some(span);
+        self
+    }
+
+    /// Points the error at `token`, which should be a slice of `line`.
+    pub fn spanning(self, line: &str, token: &str) -> ParseError {
+        match offset_in(line, token) {
+            Some(start) => self.at(start..start + token.len()),
+            None => self,
+        }
+    }
+
+    /// Points the error just past the end of `line`, for parts that are missing.
+    pub fn at_end(self, line: &str) -> ParseError {
+        self.at(line.len()..line.len())
+    }
+
+    /// Moves a span relative to `token` so it is relative to `line`, or spans the
+    /// whole token if the error didn't point anywhere yet.
+    pub fn within(self, line: &str, token: &str) -> ParseError {
+        match (offset_in(line, token), &self.span) {
+            (Some(start), Some(span)) => {
+                let span = span.start + start..span.end + start;
+                self.at(span)
+            }
+            (Some(_), None) => self.spanning(line, token),
+            (None, _) => self,
+        }
+    }
+
+    pub fn with_source(mut self, name: &str, text: &str) -> ParseError {
+        self.source = Some(Source {
+            name: name.to_owned(),
+            text: text.to_owned(),
+        });
+        self
+    }
+
+    /// 1-based column the span starts at.
+    pub fn column(&self) -> Option<usize> {
+        self.span.as_ref().map(|span| span.start + 1)
+    }
+
+    /// Renders the offending line of the attached source with the span underlined.
+    pub fn snippet(&self) -> Option<String> {
+        let source = self.source.as_ref()?;
+        let line = self.line?;
+        let text = source.text.lines().nth(line - 1).unwrap_or_default();
+        let column = self.column().unwrap_or(1);
+
+        let number = line.to_string();
+        let gutter = " ".repeat(number.len());
+        let mut snippet = format!("{}--> {}:{}:{}\n", gutter, source.name, line, column);
+        snippet += &format!("{} |\n", gutter);
+        snippet += &format!("{} | {}\n", number, text);
+        if let Some(span) = &self.span {
+            let start = text
+                .get(..span.start)
+                .map_or(span.start, |s| s.chars().count());
+            let width = text
+                .get(span.clone())
+                .map_or(span.len(), |s| s.chars().count())
+                .max(1);
+            snippet += &format!("{} | {}{}\n", gutter, " ".repeat(start), "^".repeat(width));
+        }
+        Some(snippet)
+    }
+}
+
+fn offset_in(line: &str, token: &str) -> Option<usize> {
+    let start = (token.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    (start + token.len() <= line.len()).then_some(start)
+}
+
+impl From<&str> for ParseError {
+    fn from(message: &str) -> Self {
+        ParseError::new(message)
+    }
+}
+
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        ParseError::new(message)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column()) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: ", line, column)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            _ => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Io(error) => write!(f, "i/o error: {}", error),
+            Error::MissingInput(path) => write!(f, "missing input file {}", path.display()),
+            Error::Parse(error) => write!(f, "parse error: {}", error),
+            Error::Solve(message) => write!(f, "failed to solve: {}", message),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+            Error::Cancelled => write!(f, "cancelled"),
+            Error::TimedOut(timeout) => write!(f, "timed out after {:?}", timeout),
+            Error::Fetch(message) => write!(f, "failed to fetch input: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippet() {
+        let line = "set A 1";
+        let error = ParseError::new("invalid register: A")
+            .on_line(2)
+            .spanning(line, &line[4..5])
+            .with_source("input/day18.txt", "snd a\nset A 1");
+
+        assert_eq!(error.to_string(), "line 2, column 5: invalid register: A");
+        assert_eq!(
+            error.snippet().unwrap(),
+            " --> input/day18.txt:2:5\n  |\n2 | set A 1\n  |     ^\n"
+        );
+    }
+}
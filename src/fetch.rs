@@ -0,0 +1,263 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::error::Error;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A puzzle input server, serving each day's input at `<base>/<year>/day/<N>/input` to
+/// requests carrying a `session` cookie. Only plain `http://` is spoken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Server {
+    /// `host[:port]` as given, sent as the `Host` header.
+    host: String,
+    /// `host:port` to connect to.
+    address: String,
+    /// Path of the base URL, without a trailing slash.
+    path: String,
+    session: String,
+}
+
+/// What [`fetch`] did for one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+impl Server {
+    pub fn new(base_url: &str, session: &str) -> Result<Server, Error> {
+        let rest = base_url.strip_prefix("http://").ok_or_else(|| {
+            Error::Usage(format!(
+                "Only http:// base URLs are supported, got {}",
+                base_url
+            ))
+        })?;
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        if host.is_empty() {
+            return Err(Error::Usage(format!("No host in base URL {}", base_url)));
+        }
+        let address = if host.contains(':') {
+            host.to_owned()
+        } else {
+            format!("{}:80", host)
+        };
+
+        Ok(Server {
+            host: host.to_owned(),
+            address,
+            path: path.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+        })
+    }
+
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!("http://{}{}", self.host, self.request_path(year, day))
+    }
+
+    fn request_path(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.path, year, day)
+    }
+
+    /// Downloads the input for `day` of `year`.
+    pub fn get(&self, year: u32, day: u32) -> Result<String, Error> {
+        let mut stream = TcpStream::connect(&self.address)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nCookie: session={}\r\nUser-Agent: aoc17\r\nConnection: close\r\n\r\n",
+            self.request_path(year, day),
+            self.host,
+            self.session
+        )?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        String::from_utf8(response)
+            .map_err(|_| "response is not UTF-8".to_owned())
+            .and_then(|response| body(&response))
+            .map_err(|message| Error::Fetch(format!("{}: {}", self.url(year, day), message)))
+    }
+}
+
+/// The year whose inputs used to be kept directly in the input directory.
+const FLAT_YEAR: u32 = 2017;
+
+/// Where the input for `day` of `year` is kept within `dir`: `YEAR/dayN.txt`, or
+/// `dayN.txt` for 2017 inputs still where they were before years had directories.
+pub fn cache_path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    let path = dir.join(year.to_string()).join(format!("day{}.txt", day));
+    let flat = dir.join(format!("day{}.txt", day));
+    if year == FLAT_YEAR && !path.exists() && flat.exists() {
+        flat
+    } else {
+        path
+    }
+}
+
+/// Downloads the input for `day` of `year` into `dir` unless it is already there.
+pub fn fetch(server: &Server, dir: &Path, year: u32, day: u32) -> Result<Fetched, Error> {
+    let path = cache_path(dir, year, day);
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let text = server.get(year, day)?;
+    fs::create_dir_all(dir.join(year.to_string()))?;
+    // Written under another name first so an interrupted download never looks cached.
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, text)?;
+    fs::rename(&partial, &path)?;
+    Ok(Fetched::Downloaded)
+}
+
+/// The body of a successful HTTP/1.1 response.
+fn body(response: &str) -> Result<String, String> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("incomplete response")?;
+    let mut lines = head.lines();
+    let status = lines.next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(format!("server answered {}", status));
+    }
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    if chunked {
+        dechunk(body)
+    } else {
+        Ok(body.to_owned())
+    }
+}
+
+fn dechunk(mut body: &str) -> Result<String, String> {
+    let mut text = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or("truncated chunk")?;
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size =
+            usize::from_str_radix(size, 16).map_err(|_| format!("invalid chunk size: {}", size))?;
+        if size == 0 {
+            return Ok(text);
+        }
+        text.push_str(rest.get(..size).ok_or("truncated chunk")?);
+        body = rest
+            .get(size..)
+            .and_then(|rest| rest.strip_prefix("\r\n"))
+            .ok_or("truncated chunk")?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::*;
+
+    /// Answers a single request with `response`, returning the request head it got.
+    fn mock(response: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/aoc", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(&stream);
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc17-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn base_url() {
+        let server = Server::new("http://localhost:8080/aoc/", "abc\n").unwrap();
+        assert_eq!(server.address, "localhost:8080");
+        assert_eq!(server.session, "abc");
+        assert_eq!(
+            server.url(2017, 3),
+            "http://localhost:8080/aoc/2017/day/3/input"
+        );
+
+        let server = Server::new("http://example.com", "abc").unwrap();
+        assert_eq!(server.address, "example.com:80");
+        assert_eq!(server.url(2017, 3), "http://example.com/2017/day/3/input");
+
+        assert!(Server::new("https://example.com", "abc").is_err());
+    }
+
+    #[test]
+    fn fetch_once() {
+        let (base, handle) = mock(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1\n2\n\r\n2\r\n3\n\r\n0\r\n\r\n",
+        );
+        let server = Server::new(&base, "secret").unwrap();
+        let dir = temp_dir("fetch-once");
+
+        assert_eq!(fetch(&server, &dir, 2017, 3).unwrap(), Fetched::Downloaded);
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /aoc/2017/day/3/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert_eq!(
+            fs::read_to_string(cache_path(&dir, 2017, 3)).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        // The mock is gone, so this only works without a request.
+        assert_eq!(fetch(&server, &dir, 2017, 3).unwrap(), Fetched::Cached);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn flat_inputs() {
+        let dir = temp_dir("flat-inputs");
+        fs::create_dir_all(dir.join("2017")).unwrap();
+        fs::write(dir.join("day1.txt"), "1").unwrap();
+        fs::write(dir.join("day2.txt"), "2").unwrap();
+        fs::write(dir.join("2017/day2.txt"), "2").unwrap();
+
+        assert_eq!(cache_path(&dir, 2017, 1), dir.join("day1.txt"));
+        assert_eq!(cache_path(&dir, 2017, 2), dir.join("2017/day2.txt"));
+        assert_eq!(cache_path(&dir, 2017, 3), dir.join("2017/day3.txt"));
+        assert_eq!(cache_path(&dir, 2018, 1), dir.join("2018/day1.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_refused() {
+        let (base, handle) = mock("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+        let server = Server::new(&base, "secret").unwrap();
+        let dir = temp_dir("fetch-refused");
+
+        let error = fetch(&server, &dir, 2017, 26).unwrap_err();
+        handle.join().unwrap();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "failed to fetch input: {}: server answered HTTP/1.1 404 Not Found",
+                server.url(2017, 26)
+            )
+        );
+        assert!(!cache_path(&dir, 2017, 26).exists());
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+use aoc17::{fetch, Error};
+
+#[derive(Args)]
+pub struct InputArgs {
+    /// Read the input from this file, `-` for stdin, or a directory holding `YEAR/dayN.txt` files
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Use the given text as the input
+    #[arg(long, value_name = "TEXT", conflicts_with = "input")]
+    input_str: Option<String>,
+}
+
+/// Where a day reads its puzzle input from.
+pub enum Input<'a> {
+    Dir(&'a Path),
+    File(&'a Path),
+    Stdin,
+    Text(&'a str),
+}
+
+impl InputArgs {
+    pub fn input(&self) -> Input<'_> {
+        match (&self.input, &self.input_str) {
+            (_, Some(text)) => Input::Text(text),
+            (Some(path), None) if path.as_os_str() == "-" => Input::Stdin,
+            (Some(path), None) if path.is_dir() => Input::Dir(path),
+            (Some(path), None) => Input::File(path),
+            (None, None) => Input::Dir(Path::new("input")),
+        }
+    }
+}
+
+impl Input<'_> {
+    /// Reads the input for `day` of `year`, returning a name to show in diagnostics and its lines.
+    pub fn read(&self, year: u32, day: u32) -> Result<(String, Vec<String>), Error> {
+        match self {
+            Input::Dir(dir) => read_file(&fetch::cache_path(dir, year, day)),
+            Input::File(path) => read_file(path),
+            Input::Stdin => {
+                let lines = io::stdin().lock().lines().collect::<Result<_, _>>()?;
+                Ok(("<stdin>".to_owned(), lines))
+            }
+            Input::Text(text) => Ok((
+                "<input-str>".to_owned(),
+                text.lines().map(str::to_owned).collect(),
+            )),
+        }
+    }
+
+    /// Where the known answers for `day` of `year` are kept, if anywhere.
+    pub fn answers(&self, year: u32, day: u32) -> Option<PathBuf> {
+        match self {
+            Input::Dir(dir) => Some(fetch::cache_path(dir, year, day).with_extension("answer")),
+            Input::File(path) => Some(path.with_extension("answer")),
+            Input::Stdin | Input::Text(_) => None,
+        }
+    }
+
+    /// Whether every day would read the same input.
+    pub fn is_shared(&self) -> bool {
+        !matches!(self, Input::Dir(_))
+    }
+}
+
+fn read_file(path: &Path) -> Result<(String, Vec<String>), Error> {
+    let file = fs::File::open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::MissingInput(path.to_owned()),
+        _ => Error::Io(e),
+    })?;
+    let lines = BufReader::new(file).lines().collect::<Result<_, _>>()?;
+    Ok((path.display().to_string(), lines))
+}
@@ -0,0 +1,19 @@
+//! Advent of Code solutions, usable without the `aoc17` command line.
+//!
+//! Every day lives in a module tree per year under [`days`], e.g. [`days::y2017`], and
+//! implements [`Day`]; [`days::registry`] lists them behind the object-safe [`Program`]
+//! trait so they can be run by year and day number.
+
+pub mod bench;
+pub mod check;
+pub mod days;
+pub mod error;
+pub mod fetch;
+pub mod panics;
+pub mod profile;
+pub mod runner;
+pub mod scaffold;
+pub mod select;
+
+pub use days::day::{Cancel, Context, Day, DayResult, Param, Params, Part, Program, Timings};
+pub use error::{Error, ParseError};
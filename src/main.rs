@@ -1,97 +1,551 @@
 use std::{
-    collections::HashMap,
-    fmt::Debug,
-    fs,
-    io::{BufRead, BufReader},
+    env, fs, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
     process::exit,
+    slice,
+    time::Duration,
 };
 
-mod days;
+mod debug;
+mod input;
+mod output;
 
-use day::Day;
-use days::*;
+use aoc17::{
+    bench::{self, Baseline},
+    check::{self, Answers, ExampleAnswer},
+    days::{
+        self,
+        vm::{Debuggee, Trace},
+        Entry,
+    },
+    fetch::{self, Fetched, Server},
+    panics, profile,
+    runner::{self, Options},
+    scaffold, select, Context, Error, Params, Part,
+};
+use input::{Input, InputArgs};
+use output::{Format, Printer, Record};
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Opt {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time parsing and solving over repeated runs
+    Bench(BenchArgs),
+    /// Run the worked examples from the puzzle texts and check their answers
+    Examples(ExamplesArgs),
+    /// Download the inputs that aren't in the input directory yet
+    Fetch(FetchArgs),
+    /// Start a new day: its module, registration, an example test and an empty input
+    New(NewArgs),
+    /// Step through the assembly program of a day interactively
+    Debug(DebugArgs),
+    /// Count how often each instruction of a day's assembly program runs
+    Profile(ProfileArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Days to run, e.g. `5`, `1,5,9`, `10-18`, `all`, `!15,!17`, `slow` or `fast`, in
+    /// the latest year that has them unless prefixed like `2017/5` or `2017/all`, or `list` to show the
+    /// registered days and their parameters
+    #[arg(required = true)]
+    day: Option<String>,
+
+    /// Only run the given part (1 or 2)
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+
+    /// Override a parameter of the selected days, e.g. `--param part2_bursts=100`
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, usize)>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Show how long parsing and each part took, with a summary when running several days
+    #[arg(long)]
+    time: bool,
+
+    /// Compare answers with the ones stored in `dayN.answer` next to the input, one part per line
+    #[arg(long)]
+    check: bool,
+
+    /// Run up to this many days at the same time; results are still printed in day order
+    #[arg(long, short, value_name = "N", default_value = "1")]
+    jobs: NonZeroUsize,
+
+    /// Give up on a day after this many seconds and report it as timed out
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Record every instruction the assembly interpreters execute in this file
+    #[arg(long, value_name = "PATH")]
+    trace: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Days to benchmark, using the same selectors as running them
     day: String,
+
+    /// Only benchmark the given part (1 or 2)
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+
+    /// Override a parameter of the selected days, e.g. `--param part2_bursts=100`
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, usize)>,
+
+    /// Also benchmark each day declaring the parameter with this value and show the
+    /// change against the first run, e.g. `--compare bytecode=0`
+    #[arg(long, value_name = "NAME=VALUE", value_parser = parse_param)]
+    compare: Option<(String, usize)>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Number of measured runs
+    #[arg(long, short, value_name = "N", default_value = "10")]
+    runs: NonZeroUsize,
+
+    /// Number of unmeasured runs before measuring
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    warmup: usize,
+
+    /// Show the change in median time against this baseline file
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+
+    /// Store the median times in this baseline file, replacing the entries for these days
+    #[arg(long, value_name = "PATH")]
+    save_baseline: Option<PathBuf>,
 }
 
-impl Opt {
-    fn day(&self) -> Option<i32> {
-        self.day.parse().ok()
+#[derive(Args)]
+struct ExamplesArgs {
+    /// Days whose examples to run, using the same selectors as running them
+    #[arg(default_value = "all")]
+    day: String,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// Days whose inputs to download, using the same selectors as running them
+    #[arg(default_value = "all")]
+    day: String,
+
+    /// Directory the inputs are kept in
+    #[arg(long, value_name = "DIR", default_value = "input")]
+    input: PathBuf,
+
+    /// Server to download from, e.g. `http://localhost:8080`; defaults to `$AOC_BASE_URL`
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// File holding the session cookie; defaults to `$AOC_SESSION`
+    #[arg(long, value_name = "PATH")]
+    session_file: Option<PathBuf>,
+}
+
+impl FetchArgs {
+    fn server(&self) -> Result<Server, Error> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => env::var("AOC_BASE_URL").map_err(|_| {
+                Error::Usage("No server given; pass --base-url or set AOC_BASE_URL".to_owned())
+            })?,
+        };
+        let session = match &self.session_file {
+            Some(path) => fs::read_to_string(path)?,
+            None => env::var("AOC_SESSION").map_err(|_| {
+                Error::Usage(
+                    "No session cookie given; pass --session-file or set AOC_SESSION".to_owned(),
+                )
+            })?,
+        };
+        Server::new(&base_url, &session)
     }
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day to start, as `YEAR/DAY` or just `DAY` in the latest year
+    #[arg(value_parser = parse_year_day)]
+    day: (u32, u32),
+}
 
-    fn all_days(&self) -> bool {
-        self.day == "all"
+#[derive(Args)]
+struct DebugArgs {
+    /// Day whose program to debug, e.g. `18` or `2017/23`
+    day: String,
+
+    /// Part whose program to debug (1 or 2)
+    #[arg(long, value_parser = parse_part, default_value = "1")]
+    part: Part,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct ProfileArgs {
+    /// Day whose program to profile, e.g. `18` or `2017/23`
+    day: String,
+
+    /// Part whose program to profile (1 or 2)
+    #[arg(long, value_parser = parse_part, default_value = "1")]
+    part: Part,
+
+    /// Stop after this many instructions
+    #[arg(long, default_value_t = 10_000_000)]
+    steps: u64,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn parse_year_day(s: &str) -> Result<(u32, u32), String> {
+    let (year, day) = match s.split_once('/') {
+        Some((year, day)) => (
+            year.parse()
+                .map_err(|_| format!("invalid year: {}", year))?,
+            day,
+        ),
+        None => (days::latest_year(), s),
+    };
+    let day = day.parse().map_err(|_| format!("invalid day: {}", day))?;
+    Ok((year, day))
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("expected 1 or 2, got {}", s)),
     }
 }
 
-fn default_error_handler<E: Debug, R>(error: E) -> R {
-    println!("{:#?}", error);
-    exit(1);
+fn parse_param(s: &str) -> Result<(String, usize), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got {}", s))?;
+    let value = value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", name, value))?;
+    Ok((name.to_owned(), value))
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("invalid number of seconds: {}", s))
 }
 
 fn main() {
+    panics::install_hook();
     let opt = Opt::parse();
-    let mut programs: HashMap<i32, Box<dyn Day>> = HashMap::new();
-    programs.insert(1, Box::new(day1::Instance));
-    programs.insert(2, Box::new(day2::Instance));
-    programs.insert(3, Box::new(day3::Instance));
-    programs.insert(4, Box::new(day4::Instance));
-    programs.insert(5, Box::new(day5::Instance));
-    programs.insert(6, Box::new(day6::Instance));
-    programs.insert(7, Box::new(day7::Instance));
-    programs.insert(8, Box::new(day8::Instance));
-    programs.insert(9, Box::new(day9::Instance));
-    programs.insert(10, Box::new(day10::Instance));
-    programs.insert(11, Box::new(day11::Instance));
-    programs.insert(12, Box::new(day12::Instance));
-    programs.insert(13, Box::new(day13::Instance));
-    programs.insert(14, Box::new(day14::Instance));
-    programs.insert(15, Box::new(day15::Instance));
-    programs.insert(16, Box::new(day16::Instance));
-    programs.insert(17, Box::new(day17::Instance));
-    programs.insert(18, Box::new(day18::Instance));
-    programs.insert(19, Box::new(day19::Instance));
-    programs.insert(20, Box::new(day20::Instance));
-    programs.insert(21, Box::new(day21::Instance));
-    programs.insert(22, Box::new(day22::Instance));
-    programs.insert(23, Box::new(day23::Instance));
-    programs.insert(24, Box::new(day24::Instance));
-    programs.insert(25, Box::new(day25::Instance));
-
-    let days = if opt.all_days() {
-        let mut d: Vec<_> = programs.keys().copied().collect();
-        d.sort();
-        d
-    } else if let Some(day) = opt.day() {
-        vec![day]
-    } else {
-        default_error_handler(format!("Invalid day: {}", opt.day))
+    let result = match &opt.command {
+        Some(Command::Bench(args)) => bench(args).map(|_| 0),
+        Some(Command::Examples(args)) => examples(args),
+        Some(Command::Fetch(args)) => fetch(args).map(|_| 0),
+        Some(Command::New(args)) => new(args).map(|_| 0),
+        Some(Command::Debug(args)) => debug(args).map(|_| 0),
+        Some(Command::Profile(args)) => profile(args).map(|_| 0),
+        None => run(&opt.run),
+    };
+    match result {
+        Ok(0) => {}
+        Ok(code) => exit(code),
+        Err(error) => {
+            output::print_error(&error);
+            exit(error.exit_code());
+        }
+    }
+}
+
+/// Picks the days for `selector`, making sure they don't all have to share one input.
+fn select_days(selector: &str, input: &Input) -> Result<Vec<&'static Entry>, Error> {
+    let programs = select::select(selector)?;
+    if input.is_shared() && programs.len() > 1 {
+        return Err(Error::Usage(
+            "A single input file or string can only be used with a single day".to_owned(),
+        ));
+    }
+    Ok(programs)
+}
+
+/// Collects the `--param` overrides, rejecting names none of the selected days declare.
+fn params(programs: &[&'static Entry], given: &[(String, usize)]) -> Result<Params, Error> {
+    let mut params = Params::default();
+    for (name, value) in given {
+        let declared = programs
+            .iter()
+            .any(|entry| entry.program.params().iter().any(|p| p.name == name));
+        if !declared {
+            return Err(Error::Usage(format!(
+                "None of the selected days has a parameter named `{}`",
+                name
+            )));
+        }
+        params.set(name, *value);
+    }
+    Ok(params)
+}
+
+/// Runs the selected days, returning the exit code of the first one that failed,
+/// or 1 if an answer didn't match its stored value.
+fn run(opt: &RunArgs) -> Result<i32, Error> {
+    let selector = opt.day.as_deref().unwrap_or_default();
+    if selector == "list" {
+        for entry in days::registry() {
+            println!("Day {}", entry);
+            for param in entry.program.params() {
+                let setting = format!("{}={}", param.name, param.default);
+                println!("  {:<28} {}", setting, param.description);
+            }
+        }
+        return Ok(0);
+    }
+
+    let input = opt.input.input();
+    let programs = select_days(selector, &input)?;
+    let context = Context {
+        params: params(&programs, &opt.params)?,
+        trace: opt.trace.as_deref().map(Trace::create).transpose()?,
+        ..Context::default()
+    };
+
+    let mut code = 0;
+    let mut printer = Printer::new(opt.format, opt.time);
+    printer.start();
+    let mut mismatch = false;
+    let options = Options {
+        jobs: opt.jobs,
+        part: opt.part,
+        timeout: opt.timeout,
+    };
+    let read = |entry: &Entry| input.read(entry.year, entry.day);
+    runner::run_days(&programs, &context, &options, read, |entry, result| {
+        let failure = match &result {
+            Ok(result) => result.error.as_ref(),
+            Err(error) => Some(error),
+        };
+        if let (0, Some(error)) = (code, failure) {
+            code = error.exit_code();
+        }
+
+        let mut checks = [None, None];
+        if let (true, Ok(result)) = (opt.check, &result) {
+            let answers = match input.answers(entry.year, entry.day) {
+                Some(path) => Answers::read(&path)?,
+                None => Answers::default(),
+            };
+            checks = answers.check(result);
+            mismatch |= checks.iter().flatten().any(|check| check.is_fail());
+        }
+
+        printer.record(&Record {
+            year: entry.year,
+            day: entry.day,
+            result,
+            checks,
+        });
+        Ok(())
+    })?;
+    printer.finish();
+    if let Some(trace) = &context.trace {
+        trace.finish()?;
+    }
+    Ok(if code == 0 && mismatch { 1 } else { code })
+}
+
+fn bench(opt: &BenchArgs) -> Result<(), Error> {
+    let input = opt.input.input();
+    let programs = select_days(&opt.day, &input)?;
+    let context = Context {
+        params: params(&programs, &opt.params)?,
+        ..Context::default()
     };
+    let compared = match &opt.compare {
+        Some(setting @ (name, value)) => {
+            // Checked like `--param`, then applied on top of the other overrides.
+            params(&programs, slice::from_ref(setting))?;
+            let mut compared = context.clone();
+            compared.params.set(name, *value);
+            Some((format!("{}={}", name, value), name, compared))
+        }
+        None => None,
+    };
+    let baseline = opt.baseline.as_deref().map(Baseline::load).transpose()?;
+    let mut saved = opt
+        .save_baseline
+        .as_deref()
+        .map(Baseline::load_or_default)
+        .transpose()?;
+
+    for entry in programs {
+        let (name, lines) = input.read(entry.year, entry.day)?;
+        let measure = |context| {
+            let timings = bench::bench(
+                entry.program,
+                &lines,
+                opt.part,
+                context,
+                opt.warmup,
+                opt.runs.get(),
+            )
+            .map_err(|e| e.with_source(&name, &lines.join("\n")))?;
+            Ok::<_, Error>(bench::stages(&timings))
+        };
+        let stages = measure(&context)?;
+        let heading = format!("Day {} ({} runs)", entry, opt.runs);
+        bench::print(&heading, &stages, |stage| {
+            baseline.as_ref()?.get(entry, stage)
+        });
+
+        if let Some((setting, param, context)) = &compared {
+            if entry.program.params().iter().any(|p| p.name == *param) {
+                let heading = format!("Day {} with {} ({} runs)", entry, setting, opt.runs);
+                bench::print(&heading, &measure(context)?, |stage| {
+                    bench::median(&stages, stage)
+                });
+            }
+        }
+
+        if let Some(saved) = &mut saved {
+            for (stage, stats) in &stages {
+                saved.insert(entry, stage, stats.median);
+            }
+        }
+    }
+
+    if let (Some(saved), Some(path)) = (saved, &opt.save_baseline) {
+        saved.save(path)?;
+    }
+    Ok(())
+}
+
+/// Runs the examples of the selected days, returning 1 if any of them gave a wrong answer.
+fn examples(opt: &ExamplesArgs) -> Result<i32, Error> {
+    let mut total = 0;
+    let mut failed = 0;
+    for entry in select::select(&opt.day)? {
+        let examples = entry.program.examples();
+        if examples.is_empty() {
+            continue;
+        }
 
-    for day in days {
-        println!("Day {}", day);
-        run_program(day, &programs);
+        println!("Day {}", entry);
+        for (i, example) in examples.iter().enumerate() {
+            total += 1;
+            let answers = match check::check_example(entry.program, example) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    failed += 1;
+                    println!("Example {}: error: {}", i + 1, error);
+                    continue;
+                }
+            };
+
+            let report: Vec<_> = (1..)
+                .zip(&answers)
+                .filter_map(|(n, answer)| {
+                    let ExampleAnswer { answer, check } = answer.as_ref()?;
+                    let answer = answer.as_deref().unwrap_or("no answer");
+                    Some(format!("Part {}: {} ({})", n, answer, check))
+                })
+                .collect();
+            if answers
+                .iter()
+                .flatten()
+                .any(|answer| answer.check.is_fail())
+            {
+                failed += 1;
+            }
+            println!("Example {}: {}", i + 1, report.join(", "));
+        }
         println!();
     }
+
+    println!("{} examples, {} failed", total, failed);
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+/// Downloads the inputs of the selected days that aren't cached yet. The server is
+/// only needed when something is missing.
+fn fetch(opt: &FetchArgs) -> Result<(), Error> {
+    let (cached, missing): (Vec<_>, Vec<_>) = select::select(&opt.day)?
+        .into_iter()
+        .partition(|entry| fetch::cache_path(&opt.input, entry.year, entry.day).exists());
+    for entry in cached {
+        println!("Day {}: cached", entry);
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let server = opt.server()?;
+    for entry in missing {
+        match fetch::fetch(&server, &opt.input, entry.year, entry.day)? {
+            Fetched::Downloaded => println!("Day {}: downloaded", entry),
+            Fetched::Cached => println!("Day {}: cached", entry),
+        }
+    }
+    Ok(())
 }
 
-fn run_program(day: i32, programs: &HashMap<i32, Box<dyn Day>>) {
-    let program = programs
-        .get(&day)
-        .unwrap_or_else(|| default_error_handler(format!("Undefined day: {}", day).as_str()));
-    let file_contents: Vec<String> = fs::File::open(format!("input/day{}.txt", day))
-        .and_then(|file| BufReader::new(file).lines().collect())
-        .unwrap_or_else(default_error_handler);
-    let result = program
-        .run(file_contents)
-        .unwrap_or_else(default_error_handler);
-
-    println!("Part 1: {}", result.part1);
-    if let Some(v) = result.part2 {
-        println!("Part 2: {}", v)
+/// Generates the skeleton of a new day, run from the root of the source tree.
+fn new(opt: &NewArgs) -> Result<(), Error> {
+    let (year, day) = opt.day;
+    for path in scaffold::scaffold(Path::new("."), year, day)? {
+        println!("Wrote {}", path.display());
     }
+    Ok(())
+}
+
+/// Runs the debugger on the program of a single day.
+fn debug(opt: &DebugArgs) -> Result<(), Error> {
+    let mut program = debuggee(&opt.day, opt.part, &opt.input)?;
+    debug::debug(program.as_mut(), io::stdin().lock(), &mut io::stdout())?;
+    Ok(())
+}
+
+/// Profiles the program of a single day, printing the annotated listing.
+fn profile(opt: &ProfileArgs) -> Result<(), Error> {
+    let mut program = debuggee(&opt.day, opt.part, &opt.input)?;
+    print!("{}", profile::profile(program.as_mut(), opt.steps));
+    Ok(())
+}
+
+/// The assembly program of the single day `selector` names, set up to run `part`.
+fn debuggee(selector: &str, part: Part, input: &InputArgs) -> Result<Box<dyn Debuggee>, Error> {
+    let input = input.input();
+    let [entry] = select::select(selector)?[..] else {
+        return Err(Error::Usage(
+            "Only a single day's program can be inspected".to_owned(),
+        ));
+    };
+    let (name, lines) = input.read(entry.year, entry.day)?;
+    entry
+        .program
+        .debug(&lines, part)
+        .map_err(|e| e.with_source(&name, &lines.join("\n")))?
+        .ok_or_else(|| Error::Usage(format!("Day {} has no program to inspect", entry)))
 }
@@ -0,0 +1,324 @@
+use std::{cmp::Reverse, time::Duration};
+
+use clap::ValueEnum;
+
+use aoc17::{check::Check, DayResult, Error, Timings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+const COLUMNS: [&str; 11] = [
+    "year",
+    "day",
+    "part1",
+    "part2",
+    "parse_ms",
+    "part1_ms",
+    "part2_ms",
+    "total_ms",
+    "part1_check",
+    "part2_check",
+    "error",
+];
+
+/// Everything reported for one day; `checks` are only filled in by `--check`.
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub result: Result<DayResult, Error>,
+    pub checks: [Option<Check>; 2],
+}
+
+/// Writes one record per day to stdout in the chosen [`Format`].
+pub struct Printer {
+    format: Format,
+    time: bool,
+    records: usize,
+    timings: Vec<((u32, u32), Timings)>,
+}
+
+impl Printer {
+    pub fn new(format: Format, time: bool) -> Printer {
+        Printer {
+            format,
+            time,
+            records: 0,
+            timings: vec![],
+        }
+    }
+
+    pub fn start(&self) {
+        match self.format {
+            Format::Text => {}
+            Format::Json => print!("["),
+            Format::Csv => println!("{}", COLUMNS.join(",")),
+            Format::Tsv => println!("{}", COLUMNS.join("\t")),
+        }
+    }
+
+    pub fn record(&mut self, record: &Record) {
+        match self.format {
+            Format::Text => print_text(record, self.time),
+            Format::Json => {
+                let separator = if self.records == 0 { "" } else { "," };
+                print!("{}\n  {}", separator, json_record(record));
+            }
+            Format::Csv => println!("{}", fields(record).map(csv_field).join(",")),
+            Format::Tsv => println!("{}", fields(record).map(tsv_field).join("\t")),
+        }
+        self.records += 1;
+        if let Ok(result) = &record.result {
+            self.timings
+                .push(((record.year, record.day), result.timings));
+        }
+    }
+
+    pub fn finish(mut self) {
+        match self.format {
+            Format::Text if self.time && self.records > 1 => print_timings(&mut self.timings),
+            Format::Text | Format::Csv | Format::Tsv => {}
+            Format::Json if self.records == 0 => println!("]"),
+            Format::Json => println!("\n]"),
+        }
+    }
+}
+
+pub fn print_error(error: &Error) {
+    eprintln!("error: {}", error);
+    if let Some(snippet) = error.snippet() {
+        eprint!("{}", snippet);
+    }
+}
+
+fn print_text(record: &Record, time: bool) {
+    println!("Day {}/{}", record.year, record.day);
+    match &record.result {
+        Ok(result) => {
+            let timings = &result.timings;
+            if time {
+                println!("Parse: {}", format_duration(timings.parse));
+            }
+            for (n, answer, duration, check) in [
+                (1, &result.part1, timings.part1, &record.checks[0]),
+                (2, &result.part2, timings.part2, &record.checks[1]),
+            ] {
+                let Some(v) = answer else { continue };
+                let notes = [
+                    duration.filter(|_| time).map(format_duration),
+                    check.as_ref().map(Check::to_string),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+                if notes.is_empty() {
+                    println!("Part {}: {}", n, v);
+                } else {
+                    println!("Part {}: {} ({})", n, v, notes.join(", "));
+                }
+            }
+            // Part 2's failure, with part 1's answer above it.
+            if let Some(error) = &result.error {
+                print_error(error);
+            }
+        }
+        Err(error) => print_error(error),
+    }
+    println!();
+}
+
+/// Prints a table of every day's timings, slowest first.
+fn print_timings(results: &mut [((u32, u32), Timings)]) {
+    results.sort_by_key(|(id, timings)| (Reverse(timings.total()), *id));
+
+    let optional = |duration: Option<Duration>| duration.map_or("-".to_owned(), format_duration);
+    println!(
+        "{:>7} {:>10} {:>10} {:>10} {:>10}",
+        "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+    for ((year, day), timings) in results.iter() {
+        println!(
+            "{:>7} {:>10} {:>10} {:>10} {:>10}",
+            format!("{}/{}", year, day),
+            format_duration(timings.parse),
+            optional(timings.part1),
+            optional(timings.part2),
+            format_duration(timings.total())
+        );
+    }
+    let total = results.iter().map(|(_, timings)| timings.total()).sum();
+    println!("{:>7} {:>43}", "All", format_duration(total));
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// The values for [`COLUMNS`], with `None` for anything that is missing.
+fn fields(record: &Record) -> [Option<String>; 11] {
+    let [check1, check2] = record
+        .checks
+        .clone()
+        .map(|check| check.map(|c| c.to_string()));
+    match &record.result {
+        Ok(result) => {
+            let timings = &result.timings;
+            [
+                Some(record.year.to_string()),
+                Some(record.day.to_string()),
+                result.part1.clone(),
+                result.part2.clone(),
+                Some(millis(timings.parse)),
+                timings.part1.map(millis),
+                timings.part2.map(millis),
+                Some(millis(timings.total())),
+                check1,
+                check2,
+                result.error.as_ref().map(Error::to_string),
+            ]
+        }
+        Err(error) => [
+            Some(record.year.to_string()),
+            Some(record.day.to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            check1,
+            check2,
+            Some(error.to_string()),
+        ],
+    }
+}
+
+fn json_record(record: &Record) -> String {
+    let values = fields(record);
+    let members = COLUMNS
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, (name, value))| {
+            let value = match value {
+                None => "null".to_owned(),
+                // Answers, checks and errors are strings, everything else is a number.
+                Some(v) if matches!(i, 2 | 3 | 8 | 9 | 10) => json_string(&v),
+                Some(v) => v,
+            };
+            format!("\"{}\": {}", name, value)
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", members.join(", "))
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(value: Option<String>) -> String {
+    let value = value.unwrap_or_default();
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn tsv_field(value: Option<String>) -> String {
+    value
+        .unwrap_or_default()
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let record = Record {
+            year: 2017,
+            day: 3,
+            result: Ok(DayResult {
+                part1: Some("a,\"b\"".to_owned()),
+                part2: None,
+                timings: Timings {
+                    parse: Duration::from_micros(1500),
+                    part1: Some(Duration::from_millis(2)),
+                    part2: None,
+                },
+                error: None,
+            }),
+            checks: [Some(Check::Pass), None],
+        };
+
+        assert_eq!(
+            json_record(&record),
+            "{\"year\": 2017, \"day\": 3, \"part1\": \"a,\\\"b\\\"\", \"part2\": null, \"parse_ms\": 1.500, \
+             \"part1_ms\": 2.000, \"part2_ms\": null, \"total_ms\": 3.500, \
+             \"part1_check\": \"pass\", \"part2_check\": null, \"error\": null}"
+        );
+        assert_eq!(
+            fields(&record).map(csv_field).join(","),
+            "2017,3,\"a,\"\"b\"\"\",,1.500,2.000,,3.500,pass,,"
+        );
+
+        let record = Record {
+            year: 2017,
+            day: 4,
+            result: Err(Error::solve("no\tway")),
+            checks: [None, None],
+        };
+        assert_eq!(
+            fields(&record).map(tsv_field).join("\t"),
+            "2017\t4\t\t\t\t\t\t\t\t\tfailed to solve: no\\tway"
+        );
+
+        // Part 2 failed after part 1 answered.
+        let record = Record {
+            year: 2017,
+            day: 5,
+            result: Ok(DayResult {
+                part1: Some("7".to_owned()),
+                part2: None,
+                timings: Timings {
+                    parse: Duration::from_millis(1),
+                    part1: Some(Duration::from_millis(2)),
+                    part2: None,
+                },
+                error: Some(Error::TimedOut(Duration::from_secs(1))),
+            }),
+            checks: [None, None],
+        };
+        assert_eq!(
+            fields(&record).map(csv_field).join(","),
+            "2017,5,7,,1.000,2.000,,3.000,,,timed out after 1s"
+        );
+    }
+}
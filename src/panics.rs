@@ -0,0 +1,58 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::error::Error;
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps the default panic output for the runner itself, but stays quiet for
+/// panics inside [`catch`], which are reported as errors instead.
+pub fn install_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            let location = info.location().map(|location| location.to_string());
+            LOCATION.set(location);
+        } else {
+            default(info);
+        }
+    }));
+}
+
+/// Runs `f`, turning a panic into [`Error::Panic`] with its message and location.
+pub fn catch<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+        let message = match LOCATION.take() {
+            Some(location) => format!("{} at {}", message, location),
+            None => message,
+        };
+        Err(Error::Panic(message))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_panics() {
+        let result: Result<(), Error> = catch(|| panic!("boom {}", 1));
+        assert!(matches!(result, Err(Error::Panic(message)) if message.starts_with("boom 1")));
+
+        assert_eq!(catch(|| Ok(1)).unwrap(), 1);
+    }
+}
@@ -0,0 +1,260 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::days::vm::{Debuggee, Executed, Status};
+
+/// How often each instruction of a program ran, and which jumps went backwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub listing: Vec<String>,
+    /// Executions by program counter.
+    pub counts: Vec<u64>,
+    /// Backward jumps taken, from the jump to its target, with how often each was taken.
+    pub back_edges: BTreeMap<(usize, usize), u64>,
+    pub steps: u64,
+    /// Why profiling stopped: `Running` if the step limit was reached.
+    pub status: Status,
+}
+
+/// The instructions from a jump target up to the backward jump returning to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+    /// Times the backward jump was taken.
+    pub repeats: u64,
+    /// Instructions executed within the loop, nested loops included.
+    pub executed: u64,
+}
+
+impl Loop {
+    fn contains(&self, other: &Loop) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// Runs `program` until it blocks or halts, or for at most `limit` instructions,
+/// counting the executions of every instruction.
+pub fn profile(program: &mut dyn Debuggee, limit: u64) -> Profile {
+    let listing = program.listing();
+    let mut counts = vec![0; listing.len()];
+    let mut back_edges = BTreeMap::new();
+    let mut steps = 0;
+    let mut status = Status::Running;
+
+    while steps < limit {
+        let stepped = program.step();
+        if let Some(Executed { pc, next, .. }) = stepped.executed {
+            counts[pc] += 1;
+            steps += 1;
+            if next <= pc {
+                *back_edges.entry((pc, next)).or_insert(0) += 1;
+            }
+        }
+        status = stepped.status;
+        if status != Status::Running {
+            break;
+        }
+    }
+
+    Profile {
+        listing,
+        counts,
+        back_edges,
+        steps,
+        status,
+    }
+}
+
+impl Profile {
+    /// Every loop taken at least once, outer loops before the loops they contain.
+    /// Jumps back to the same target make up one loop ending at the last of them.
+    pub fn loops(&self) -> Vec<Loop> {
+        let mut loops: BTreeMap<usize, Loop> = BTreeMap::new();
+        for (&(end, start), &repeats) in &self.back_edges {
+            let entry = loops.entry(start).or_insert(Loop {
+                start,
+                end,
+                repeats: 0,
+                executed: 0,
+            });
+            entry.end = entry.end.max(end);
+            entry.repeats += repeats;
+        }
+
+        let mut loops: Vec<Loop> = loops
+            .into_values()
+            .map(|l| Loop {
+                executed: self.counts[l.start..=l.end].iter().sum(),
+                ..l
+            })
+            .collect();
+        loops.sort_by_key(|l| (l.start, std::cmp::Reverse(l.end)));
+        loops
+    }
+
+    /// The loop executing the most instructions among those containing no other loop,
+    /// preceded by the loops around it, outermost first.
+    pub fn hottest_nest(&self) -> Vec<Loop> {
+        let loops = self.loops();
+        let innermost = loops
+            .iter()
+            .filter(|l| !loops.iter().any(|other| other != *l && l.contains(other)))
+            .max_by_key(|l| l.executed);
+        match innermost {
+            Some(innermost) => loops
+                .iter()
+                .filter(|l| l.contains(innermost))
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn share(&self, count: u64) -> f64 {
+        if self.steps == 0 {
+            0.0
+        } else {
+            100.0 * count as f64 / self.steps as f64
+        }
+    }
+}
+
+/// The listing annotated with counts, one `|` per loop around each instruction and
+/// the backward jumps taken, followed by the hottest loop nest.
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stopped = match self.status {
+            Status::Running => "stopped at the limit",
+            Status::Blocked => "blocked",
+            Status::Halted => "halted",
+        };
+        writeln!(f, "{} instructions executed, {}", self.steps, stopped)?;
+        writeln!(f, "{:>12} {:>7} {:>4}  instruction", "count", "share", "pc")?;
+
+        let loops = self.loops();
+        for (pc, instruction) in self.listing.iter().enumerate() {
+            let depth = loops
+                .iter()
+                .filter(|l| (l.start..=l.end).contains(&pc))
+                .count();
+            write!(
+                f,
+                "{:>12} {:>6.2}% {:>4}  {}{}",
+                self.counts[pc],
+                self.share(self.counts[pc]),
+                pc,
+                "| ".repeat(depth),
+                instruction
+            )?;
+            for (&(_, to), repeats) in self.back_edges.range((pc, 0)..=(pc, usize::MAX)) {
+                write!(f, "  <- back to {}, {} times", to, repeats)?;
+            }
+            writeln!(f)?;
+        }
+
+        let nest = self.hottest_nest();
+        if nest.is_empty() {
+            return writeln!(f, "No loops taken");
+        }
+        writeln!(f, "Hottest loop nest:")?;
+        for (depth, l) in nest.iter().enumerate() {
+            writeln!(
+                f,
+                "{}pc {}-{}: {} instructions ({:.2}%), repeated {} times",
+                "  ".repeat(depth + 1),
+                l.start,
+                l.end,
+                l.executed,
+                self.share(l.executed),
+                l.repeats
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, day::Part};
+
+    fn coprocessor(program: &[&str]) -> Box<dyn Debuggee> {
+        let lines: Vec<_> = program.iter().map(|line| line.to_string()).collect();
+        days::get(2017, 23)
+            .unwrap()
+            .program
+            .debug(&lines, Part::One)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn nested_loops() {
+        let mut program = coprocessor(&[
+            "set a 2", "set b 3", "sub b 1", "jnz b -1", "sub a 1", "jnz a -4", "mul a a",
+        ]);
+        let profile = profile(program.as_mut(), 1000);
+
+        assert_eq!(profile.status, Status::Halted);
+        assert_eq!(profile.counts, [1, 2, 6, 6, 2, 2, 1]);
+        assert_eq!(profile.steps, 20);
+        assert_eq!(
+            profile.back_edges,
+            BTreeMap::from([((3, 2), 4), ((5, 1), 1)])
+        );
+        let outer = Loop {
+            start: 1,
+            end: 5,
+            repeats: 1,
+            executed: 18,
+        };
+        let inner = Loop {
+            start: 2,
+            end: 3,
+            repeats: 4,
+            executed: 12,
+        };
+        assert_eq!(profile.loops(), [outer, inner]);
+        assert_eq!(profile.hottest_nest(), [outer, inner]);
+        assert!(profile
+            .to_string()
+            .contains("           6  30.00%    3  | | jnz b -1  <- back to 2, 4 times\n"));
+    }
+
+    #[test]
+    fn duet() {
+        let lines = [
+            "snd 1", "snd 2", "snd p", "rcv a", "rcv b", "rcv c", "rcv d",
+        ]
+        .map(str::to_owned);
+        let mut program = days::get(2017, 18)
+            .unwrap()
+            .program
+            .debug(&lines, Part::Two)
+            .unwrap()
+            .unwrap();
+        let profile = profile(program.as_mut(), 1000);
+
+        assert_eq!(profile.status, Status::Blocked);
+        assert_eq!(profile.steps, 12);
+        assert_eq!(profile.counts, [2, 2, 2, 2, 2, 2, 0]);
+    }
+
+    #[test]
+    fn empty() {
+        let profile = profile(coprocessor(&[]).as_mut(), 10);
+        assert_eq!(profile.status, Status::Halted);
+        assert_eq!(profile.steps, 0);
+        assert!(profile.to_string().ends_with("No loops taken\n"));
+    }
+
+    #[test]
+    fn step_limit() {
+        let mut program = coprocessor(&["jnz 1 0"]);
+        let profile = profile(program.as_mut(), 10);
+
+        assert_eq!(profile.status, Status::Running);
+        assert_eq!(profile.counts, [10]);
+        assert_eq!(profile.hottest_nest()[0].repeats, 10);
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days::{
+        day::{Cancel, Context, DayResult, Part},
+        Entry,
+    },
+    error::Error,
+    panics,
+};
+
+/// How [`run_days`] runs the days it is given.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Days run at the same time.
+    pub jobs: NonZeroUsize,
+    /// The part to run, or both.
+    pub part: Option<Part>,
+    /// How long a day may run before it is reported as timed out.
+    pub timeout: Option<Duration>,
+}
+
+/// Runs `programs` on up to `options.jobs` threads, reading each day's input with `read`
+/// and handing each result to `report` in the order the programs were given. Stops
+/// starting new days once `report` fails.
+pub fn run_days<R>(
+    programs: &[&'static Entry],
+    context: &Context,
+    options: &Options,
+    read: R,
+    mut report: impl FnMut(&Entry, Result<DayResult, Error>) -> Result<(), Error>,
+) -> Result<(), Error>
+where
+    R: Fn(&Entry) -> Result<(String, Vec<String>), Error> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.get().min(programs.len()) {
+            let sender = sender.clone();
+            let (next, read) = (&next, &read);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(entry) = programs.get(i) else { break };
+                let result =
+                    read(entry).and_then(|input| run_program(entry, input, context, options));
+                if sender.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut reported = 0;
+        for (i, result) in receiver {
+            finished.insert(i, result);
+            while let Some(result) = finished.remove(&reported) {
+                if let Err(error) = report(programs[reported], result) {
+                    next.store(programs.len(), Ordering::Relaxed);
+                    return Err(error);
+                }
+                reported += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+fn run_program(
+    entry: &'static Entry,
+    (name, lines): (String, Vec<String>),
+    context: &Context,
+    options: &Options,
+) -> Result<DayResult, Error> {
+    let part = options.part;
+    // Each day gets its own cancellation so a timeout only stops that day, and its
+    // trace lines say which day wrote them.
+    let context = Context {
+        cancel: Cancel::default(),
+        trace: context
+            .trace
+            .as_ref()
+            .map(|trace| trace.labelled(&entry.to_string())),
+        ..context.clone()
+    };
+
+    let Some(timeout) = options.timeout else {
+        let result = panics::catch(|| entry.program.run(&lines, part, &context));
+        return with_source(result, &name, &lines);
+    };
+
+    // The day runs on its own thread so we can stop waiting for it. If it doesn't
+    // notice the cancellation it is left running until the process exits. Part 1's
+    // answer is sent ahead, so a timeout in part 2 still reports it.
+    let (sender, receiver) = mpsc::channel();
+    let day_context = context.clone();
+    thread::spawn(move || {
+        let ahead = sender.clone();
+        let result = panics::catch(|| {
+            entry
+                .program
+                .run_partial(&lines, part, &day_context, &mut |result| {
+                    let _ = ahead.send(Progress::Partial(DayResult {
+                        part1: result.part1.clone(),
+                        part2: result.part2.clone(),
+                        timings: result.timings,
+                        error: None,
+                    }));
+                })
+        });
+        let _ = sender.send(Progress::Done(result, lines));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let mut partial = None;
+    loop {
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Progress::Partial(result)) => partial = Some(result),
+            Ok(Progress::Done(result, lines)) => return with_source(result, &name, &lines),
+            Err(RecvTimeoutError::Timeout) => {
+                context.cancel.cancel();
+                let error = Error::TimedOut(timeout);
+                return match partial {
+                    Some(result) => Ok(DayResult {
+                        error: Some(error),
+                        ..result
+                    }),
+                    None => Err(error),
+                };
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(Error::Panic("day thread died".to_owned()))
+            }
+        }
+    }
+}
+
+/// What a day's thread sends back to [`run_program`].
+enum Progress {
+    /// Part 1's answer, sent before part 2 starts.
+    Partial(DayResult),
+    Done(Result<DayResult, Error>, Vec<String>),
+}
+
+/// Points errors, including the one part 2 left next to part 1's answer, at the input.
+fn with_source(
+    result: Result<DayResult, Error>,
+    name: &str,
+    lines: &[String],
+) -> Result<DayResult, Error> {
+    let source = || lines.join("\n");
+    match result {
+        Ok(result) => Ok(DayResult {
+            error: result.error.map(|e| e.with_source(name, &source())),
+            ..result
+        }),
+        Err(error) => Err(error.with_source(name, &source())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day::{Day, ParseError};
+
+    /// Sleeps for as many milliseconds as its input says, then answers with that number.
+    struct Sleep;
+
+    impl Day for Sleep {
+        type Input = u64;
+
+        fn parse(&self, lines: &[String]) -> Result<u64, ParseError> {
+            lines[0]
+                .parse()
+                .map_err(|_| ParseError::new("not a number").on_line(1))
+        }
+
+        fn part1(&self, millis: &u64, _context: &Context) -> Result<String, Error> {
+            thread::sleep(Duration::from_millis(*millis));
+            Ok(millis.to_string())
+        }
+    }
+
+    #[test]
+    fn reports_in_order() {
+        static ENTRIES: [Entry; 4] = [
+            Entry {
+                year: 2017,
+                day: 1,
+                program: &Sleep,
+            },
+            Entry {
+                year: 2017,
+                day: 2,
+                program: &Sleep,
+            },
+            Entry {
+                year: 2017,
+                day: 3,
+                program: &Sleep,
+            },
+            Entry {
+                year: 2017,
+                day: 4,
+                program: &Sleep,
+            },
+        ];
+        let programs: Vec<_> = ENTRIES.iter().collect();
+        let options = Options {
+            jobs: NonZeroUsize::new(4).unwrap(),
+            part: None,
+            timeout: None,
+        };
+        // Later days sleep less, so they finish first.
+        let read = |entry: &Entry| {
+            let millis = (4 - entry.day) * 50;
+            Ok((entry.to_string(), vec![millis.to_string()]))
+        };
+
+        let mut reported = vec![];
+        run_days(
+            &programs,
+            &Context::default(),
+            &options,
+            read,
+            |entry, result| {
+                reported.push((entry.day, result?.part1.unwrap()));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            reported,
+            [(1, "150"), (2, "100"), (3, "50"), (4, "0")]
+                .map(|(day, answer)| (day, answer.to_owned()))
+        );
+    }
+}
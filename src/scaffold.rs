@@ -0,0 +1,99 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+/// Starts day `day` of `year` in the source tree at `root`: a day module following
+/// the `Instance` pattern, which the build script declares and registers, and an
+/// empty input. Returns the files created; existing day files are never overwritten.
+pub fn scaffold(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!(
+            "Invalid day: {}, expected 1 to 25",
+            day
+        )));
+    }
+    if !(1000..=9999).contains(&year) {
+        return Err(Error::Usage(format!("Invalid year: {}", year)));
+    }
+
+    let days = root.join("src/days");
+    let year_dir = days.join(format!("y{}", year));
+    let source = year_dir.join(format!("day{}.rs", day));
+    if source.exists() {
+        return Err(Error::Usage(format!("{} already exists", source.display())));
+    }
+
+    let mut changed = Vec::new();
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&source, template())?;
+    changed.push(source);
+
+    let inputs = root.join("input").join(year.to_string());
+    let input = inputs.join(format!("day{}.txt", day));
+    if !input.exists() {
+        fs::create_dir_all(&inputs)?;
+        fs::write(&input, "")?;
+        changed.push(input);
+    }
+    Ok(changed)
+}
+
+fn template() -> &'static str {
+    r#"use crate::days::day::*;
+
+pub struct Instance;
+
+impl Day for Instance {
+    type Input = Vec<String>;
+    // e.g. Example::new("input", "part 1 answer", "part 2 answer")
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, ParseError> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(&self, _input: &Self::Input, _context: &Context) -> Result<String, Error> {
+        Err(Error::solve("not solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_examples(&Instance);
+    }
+}
+"#
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn scaffold_day() {
+        let root = env::temp_dir().join(format!("aoc17-scaffold-{}", process::id()));
+        let source = root.join("src/days/y2018/day3.rs");
+        let input = root.join("input/2018/day3.txt");
+
+        let changed = scaffold(&root, 2018, 3).unwrap();
+        assert_eq!(changed, [source.clone(), input.clone()]);
+        assert_eq!(fs::read_to_string(&source).unwrap(), template());
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+
+        // Started days are never overwritten.
+        fs::write(&source, "// solved").unwrap();
+        assert!(matches!(scaffold(&root, 2018, 3), Err(Error::Usage(_))));
+        assert_eq!(fs::read_to_string(&source).unwrap(), "// solved");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
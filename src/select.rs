@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    days::{self, Entry},
+    error::Error,
+};
+
+/// Picks registered days from a comma separated selector, ordered by year and day.
+///
+/// Each item is a day (`5`), an inclusive range (`10-18`), `all`, or one of the
+/// `slow`/`fast` tags. Days and ranges are in the [default year](days::default_year)
+/// unless written as `2017/5` or `2017/10-18`; `2017/all`, `2017/slow` and
+/// `2017/fast` limit the others to one year. Prefixing an item with `!` removes
+/// those days instead; a selector made only of exclusions starts from every day.
+pub fn select(selector: &str) -> Result<Vec<&'static Entry>, Error> {
+    let mut included = BTreeSet::new();
+    let mut excluded = BTreeSet::new();
+    let mut only_exclusions = true;
+
+    for item in selector.split(',').map(str::trim) {
+        match item.strip_prefix('!') {
+            Some(item) => excluded.extend(resolve(item)?),
+            None => {
+                only_exclusions = false;
+                included.extend(resolve(item)?);
+            }
+        }
+    }
+    if only_exclusions {
+        included.extend(days::registry().iter().map(key));
+    }
+
+    let selected: Vec<_> = days::registry()
+        .iter()
+        .filter(|entry| included.contains(&key(entry)) && !excluded.contains(&key(entry)))
+        .collect();
+    if selected.is_empty() {
+        return Err(Error::Usage(format!("No days match {}", selector)));
+    }
+    Ok(selected)
+}
+
+fn key(entry: &Entry) -> (u32, u32) {
+    (entry.year, entry.day)
+}
+
+fn resolve(item: &str) -> Result<Vec<(u32, u32)>, Error> {
+    let (year, days) = match item.split_once('/') {
+        Some((year, days)) => (Some(number(year, "year")?), days.trim()),
+        None => (None, item),
+    };
+    let matching = |keep: &dyn Fn(&Entry) -> bool| {
+        days::registry()
+            .iter()
+            .filter(|entry| year.is_none_or(|year| entry.year == year) && keep(entry))
+            .map(key)
+            .collect()
+    };
+
+    match days {
+        "all" => Ok(matching(&|_| true)),
+        "slow" => Ok(matching(&|entry| entry.program.slow())),
+        "fast" => Ok(matching(&|entry| !entry.program.slow())),
+        _ => match days.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (number(from, "day")?, number(to, "day")?);
+                if from > to {
+                    return Err(Error::Usage(format!("Invalid day range: {}", item)));
+                }
+                let year = year.unwrap_or_else(|| days::default_year(from..=to));
+                Ok(matching(&|entry| {
+                    entry.year == year && (from..=to).contains(&entry.day)
+                }))
+            }
+            None => {
+                let day = number(days, "day")?;
+                let year = year.unwrap_or_else(|| days::default_year(day..=day));
+                days::get(year, day)
+                    .map(|entry| vec![key(entry)])
+                    .ok_or_else(|| Error::Usage(format!("Undefined day: {}", item)))
+            }
+        },
+    }
+}
+
+fn number(s: &str, what: &str) -> Result<u32, Error> {
+    s.trim()
+        .parse()
+        .map_err(|_| Error::Usage(format!("Invalid {}: {}", what, s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(selector: &str) -> Vec<u32> {
+        select(selector)
+            .unwrap()
+            .iter()
+            .map(|entry| entry.day)
+            .collect()
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(days("2017/9,2017/1,2017/5,2017/5"), vec![1, 5, 9]);
+        assert_eq!(days("2017/10-12,2017/2"), vec![2, 10, 11, 12]);
+        assert_eq!(days("all").len(), days::registry().len());
+        assert_eq!(days("!2017/15,!2017/17").len(), days::registry().len() - 2);
+        assert_eq!(days("2017/1-5,!2017/2-3"), vec![1, 4, 5]);
+        assert!(days("2017/fast")
+            .iter()
+            .all(|day| !days("2017/slow").contains(day)));
+        assert!(days("2017/slow").contains(&22));
+        assert_eq!(days("2017/slow,!2017/13-21"), vec![22]);
+
+        assert!(select("2017/3-1").is_err());
+        assert!(select("2017/0").is_err());
+        assert!(select("2017/1,x").is_err());
+        assert!(select("2017/1,!2017/1").is_err());
+        assert!(select("1999/all").is_err());
+        assert!(select("x/1").is_err());
+    }
+
+    #[test]
+    fn default_year() {
+        let year = days::default_year(1..=3);
+        let selected = select("1-3").unwrap();
+        assert!(selected.iter().all(|entry| entry.year == year));
+        assert!(select(&format!("1,!{}/1", year)).is_err());
+    }
+}
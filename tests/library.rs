@@ -0,0 +1,103 @@
+use aoc17::{
+    days::{
+        self,
+        y2017::{day25::TuringMachine, knot::Ring},
+    },
+    Cancel, Context, Day, Error, ParseError, Part, Program,
+};
+
+/// Answers part 1 and fails part 2 the way its input says.
+struct FailingPart2;
+
+impl Day for FailingPart2 {
+    type Input = String;
+
+    fn parse(&self, lines: &[String]) -> Result<String, ParseError> {
+        Ok(lines.join(""))
+    }
+
+    fn part1(&self, _input: &String, _context: &Context) -> Result<String, Error> {
+        Ok("1".to_owned())
+    }
+
+    fn part2(&self, input: &String, _context: &Context) -> Result<Option<String>, Error> {
+        match input.as_str() {
+            "panic" => panic!("part 2 panicked"),
+            _ => Err(Error::solve("part 2 failed")),
+        }
+    }
+}
+
+#[test]
+fn run_registered_day() {
+    let entry = days::get(2017, 1).unwrap();
+    let lines = vec!["91212129".to_owned()];
+    let result = entry
+        .program
+        .run(&lines, Some(Part::One), &Context::default())
+        .unwrap();
+    assert_eq!(result.part1.as_deref(), Some("9"));
+    assert_eq!(result.part2, None);
+}
+
+#[test]
+fn part2_failure_keeps_part1() {
+    for (input, message) in [("error", "part 2 failed"), ("panic", "part 2 panicked")] {
+        let mut partial = None;
+        let result = FailingPart2
+            .run_partial(
+                &[input.to_owned()],
+                None,
+                &Context::default(),
+                &mut |result| partial = result.part1.clone(),
+            )
+            .unwrap();
+        assert_eq!(partial.as_deref(), Some("1"));
+        assert_eq!(result.part1.as_deref(), Some("1"));
+        assert_eq!(result.part2, None);
+        assert!(result.error.unwrap().to_string().contains(message));
+    }
+
+    // Without part 1's answer to keep, part 2's failure is the result.
+    let result = FailingPart2.run(&["error".to_owned()], Some(Part::Two), &Context::default());
+    assert!(matches!(result, Err(Error::Solve(_))));
+}
+
+#[test]
+fn use_day_directly() {
+    let day = days::y2017::day2::Instance;
+    let input = day.parse(&["5 1 9 5".to_owned()]).unwrap();
+    assert_eq!(day.part1(&input, &Context::default()).unwrap(), "8");
+}
+
+#[test]
+fn engines() {
+    let mut ring = Ring::new(4);
+    ring.encode(&[3, 4, 1, 5]);
+    assert_eq!(ring.first_two(), 12);
+
+    let blueprint = "Begin in state A.
+Perform a diagnostic checksum after 6 steps.
+
+In state A:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state B.
+  If the current value is 1:
+    - Write the value 0.
+    - Move one slot to the left.
+    - Continue with state B.
+
+In state B:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the left.
+    - Continue with state A.
+  If the current value is 1:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state A.";
+    let mut machine: TuringMachine = blueprint.parse().unwrap();
+    assert_eq!(machine.checksum(&Cancel::default()).unwrap(), 3);
+}